@@ -0,0 +1,55 @@
+use chrono::{DateTime, Local, Utc};
+
+/// Current time as unix seconds
+pub fn now() -> i64 {
+    Utc::now().timestamp()
+}
+
+/// Compact relative age like "5m", "3h", "3d", "2w", "4mo", "1y"
+pub fn format_age(timestamp: i64, now: i64) -> String {
+    let secs = (now - timestamp).max(0);
+    let minutes = secs / 60;
+    let hours = minutes / 60;
+    let days = hours / 24;
+
+    if minutes < 1 {
+        "now".to_string()
+    } else if hours < 1 {
+        format!("{}m", minutes)
+    } else if days < 1 {
+        format!("{}h", hours)
+    } else if days < 14 {
+        format!("{}d", days)
+    } else if days < 60 {
+        format!("{}w", days / 7)
+    } else if days < 365 {
+        format!("{}mo", days / 30)
+    } else {
+        format!("{}y", days / 365)
+    }
+}
+
+/// Local date and time, e.g. "2024-03-01 14:05"
+pub fn format_date(timestamp: i64) -> String {
+    DateTime::from_timestamp(timestamp, 0)
+        .map(|dt| dt.with_timezone(&Local).format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_age() {
+        let now = 1_000_000_000;
+        assert_eq!(format_age(now - 30, now), "now");
+        assert_eq!(format_age(now - 5 * 60, now), "5m");
+        assert_eq!(format_age(now - 3 * 3600, now), "3h");
+        assert_eq!(format_age(now - 3 * 86400, now), "3d");
+        assert_eq!(format_age(now - 15 * 86400, now), "2w");
+        assert_eq!(format_age(now - 120 * 86400, now), "4mo");
+        assert_eq!(format_age(now - 400 * 86400, now), "1y");
+        assert_eq!(format_age(now + 60, now), "now");
+    }
+}
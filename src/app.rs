@@ -1,14 +1,23 @@
-use std::path::PathBuf;
+use std::cell::Cell;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Receiver, TryRecvError};
 use std::time::{Duration, Instant};
 
 use anyhow::Result;
-use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseEventKind};
 use ratatui::widgets::ListState;
 use ratatui::DefaultTerminal;
 
-use crate::config::{CommandMode, Config, Shortcut};
-use crate::git::Worktree;
+use crate::archive::{Archive, ArchiveEntry};
+use crate::cache::Cache;
+use crate::config::{CommandMode, Config, NotesStorage, Shortcut};
+use crate::editor::TextEditor;
+use crate::filter::Filter;
+use crate::state::State;
+use crate::git::{ChangedFile, Commit, Stash, Worktree};
 use crate::ui;
+use crate::watch::Watcher;
 
 #[derive(Default, PartialEq)]
 pub enum AppMode {
@@ -18,6 +27,28 @@ pub enum AppMode {
     ConfirmDelete,
     Deleting,
     Help,
+    Filtering,
+    Tasks,
+    AddingTask,
+    EditingSection,
+    History,
+    Log,
+    Changes,
+    ConfirmDiscard,
+    Committing,
+    Stashes,
+    ConfirmAbort,
+}
+
+/// A line in the worktree list: either a group header or a worktree
+#[derive(Clone, PartialEq)]
+pub enum ListRow {
+    Group {
+        name: String,
+        count: usize,
+        collapsed: bool,
+    },
+    Worktree(usize), // index into `App::worktrees`
 }
 
 #[derive(Default, Clone, Copy, PartialEq)]
@@ -25,6 +56,14 @@ pub enum DetailViewMode {
     #[default]
     Notes,
     GitStatus,
+    Log,
+}
+
+/// What the diff in the changes view shows
+#[derive(Clone, PartialEq)]
+pub enum DiffTarget {
+    File(ChangedFile),
+    Branch, // everything since the branch left main
 }
 
 pub struct App {
@@ -38,17 +77,65 @@ pub struct App {
     pub input_cursor: usize,
     pub should_quit: bool,
     pub error: Option<String>,
-    pub repo_path: PathBuf,
+    pub notice: Option<String>,
+    pub repo_path: PathBuf, // main worktree (or bare repo) of the repository wtm runs in
+    start_dir: PathBuf,     // where wtm was started, to preselect that worktree
+    pub dashboard: bool, // worktrees of all configured repos, grouped by repo
     pub branches: Vec<String>,
     pub filtered_branches: Vec<String>,
     pub exit_path: Option<PathBuf>,
     pub needs_full_redraw: bool,
     pub config: Config,
+    pub filter_input: String,
+    pub filter_cursor: usize,
+    pub filter: Filter,
+    pub visible: Vec<usize>, // indices into `worktrees` that pass the filter, in display order
+    pub rows: Vec<ListRow>,
+    pub state: State,
+    pub detail_scroll: u16,
+    /// Scroll limit and page size of the detail pane, updated on each render
+    pub detail_max_scroll: Cell<u16>,
+    pub detail_page: Cell<u16>,
+    pub task_cursor: usize, // index into the tasks of the selected status file
+    pub commits: Vec<Commit>, // branch log, loaded in the Log view
+    pub log_cursor: usize,
+    pub commit_diff: Option<String>, // `git show` of the commit opened from the log
+    pub changed_files: Vec<ChangedFile>,
+    pub file_cursor: usize,
+    pub diff_target: Option<DiffTarget>,
+    pub file_diff: Option<String>,
+    pub word_diff: bool,
+    pub stashes: Vec<Stash>, // all stashes of the repo, loaded for the stash view
+    pub stash_cursor: usize,
+    pub stash_all: bool, // show every stash instead of the selected branch's
+    pub stash_diff: Option<String>,
+    pub stash_scroll: u16,
+    pub confirm_drop: bool,
+    pub editor: TextEditor,
+    pub editing_section: &'static str, // status file section being edited inline
+    pub templates: Vec<String>,          // status template names, for the create dialog
+    pub template_choice: Option<usize>,  // index into `templates`, None to pick by branch
+    pub archive: Archive,
+    pub history_query: String,
+    pub history_cursor: usize, // index into `history_matches()`
+    pub history_scroll: u16,
+    watcher: Option<Watcher>, // None when the platform can't watch files
+    /// Worktrees being re-checked after starting from the cache; rows are
+    /// shown as stale until this finishes
+    pub revalidating: Option<Receiver<Result<Vec<Worktree>>>>,
+    /// Worktrees updated while revalidating, whose result would be older
+    updated_while_revalidating: HashSet<PathBuf>,
 }
 
 impl App {
-    pub fn new() -> Result<Self> {
-        let repo_path = std::env::current_dir()?;
+    /// `start_dir` can be anywhere in any worktree of the repository; outside
+    /// a repository only the dashboard works
+    pub fn new(start_dir: &Path, dashboard: bool) -> Result<Self> {
+        let repo_path = match crate::git::main_repo(start_dir) {
+            Ok(repo_path) => repo_path,
+            Err(_) if dashboard => start_dir.to_path_buf(),
+            Err(e) => return Err(e),
+        };
         let config = match Config::load() {
             Ok(c) => c,
             Err(e) => {
@@ -67,12 +154,50 @@ impl App {
             input_cursor: 0,
             should_quit: false,
             error: None,
+            notice: None,
             repo_path,
+            start_dir: start_dir.to_path_buf(),
+            dashboard,
             branches: Vec::new(),
             filtered_branches: Vec::new(),
             exit_path: None,
             needs_full_redraw: false,
             config,
+            filter_input: String::new(),
+            filter_cursor: 0,
+            filter: Filter::default(),
+            visible: Vec::new(),
+            rows: Vec::new(),
+            state: State::load(),
+            detail_scroll: 0,
+            detail_max_scroll: Cell::new(0),
+            detail_page: Cell::new(0),
+            task_cursor: 0,
+            commits: Vec::new(),
+            log_cursor: 0,
+            commit_diff: None,
+            changed_files: Vec::new(),
+            file_cursor: 0,
+            diff_target: None,
+            file_diff: None,
+            word_diff: false,
+            stashes: Vec::new(),
+            stash_cursor: 0,
+            stash_all: false,
+            stash_diff: None,
+            stash_scroll: 0,
+            confirm_drop: false,
+            editor: TextEditor::default(),
+            editing_section: "",
+            templates: Vec::new(),
+            template_choice: None,
+            archive: Archive::default(),
+            history_query: String::new(),
+            history_cursor: 0,
+            history_scroll: 0,
+            watcher: None,
+            revalidating: None,
+            updated_while_revalidating: HashSet::new(),
         };
         app.list_state.select(Some(0));
         Ok(app)
@@ -82,9 +207,21 @@ impl App {
         // Clear screen on startup to remove any previous terminal content
         terminal.clear()?;
 
+        // Keep notes in the worktree root out of `git status`
+        if self.config.exclude_status_file && self.config.notes_storage == NotesStorage::Worktree {
+            for repo in self.repos() {
+                if let Err(e) = crate::git::add_exclude(&repo, crate::status::STATUS_FILE) {
+                    self.error = Some(format!("Failed to update .git/info/exclude: {}", e));
+                }
+            }
+        }
+
         // Initial load
-        self.refresh_worktrees();
+        self.watcher = Watcher::new().ok();
+        self.load_cached_worktrees();
+        self.select_start_worktree();
         self.refresh_branches();
+        self.check_status_migration();
 
         let tick_rate = Duration::from_millis(250);
         let mut last_tick = Instant::now();
@@ -114,30 +251,58 @@ impl App {
             // Tick
             if last_tick.elapsed() >= tick_rate {
                 last_tick = Instant::now();
+                if !self.is_using_selection() {
+                    self.finish_revalidation();
+                    self.reload_changed_worktrees();
+                }
             }
         }
 
+        self.save_cache();
         Ok(())
     }
 
     fn handle_event(&mut self, event: Event) -> Result<()> {
-        if let Event::Key(key) = event {
-            if key.kind == KeyEventKind::Press {
+        match event {
+            Event::Key(key) if key.kind == KeyEventKind::Press => {
                 // Clear error on any keypress
                 self.error = None;
-                self.handle_key(key.code)?;
+                self.notice = None;
+                self.handle_key(key)?;
             }
+            Event::Mouse(mouse) => match mouse.kind {
+                MouseEventKind::ScrollDown => self.scroll_detail(3),
+                MouseEventKind::ScrollUp => self.scroll_detail(-3),
+                _ => {}
+            },
+            _ => {}
         }
         Ok(())
     }
 
-    fn handle_key(&mut self, key: KeyCode) -> Result<()> {
+    fn handle_key(&mut self, key: KeyEvent) -> Result<()> {
+        if self.mode == AppMode::EditingSection {
+            return self.handle_editing_key(key);
+        }
+
+        let key = key.code;
         match self.mode {
             AppMode::Normal => self.handle_normal_key(key),
             AppMode::Creating => self.handle_creating_key(key),
             AppMode::ConfirmDelete => self.handle_delete_key(key),
             AppMode::Deleting => Ok(()), // Ignore input while deleting
             AppMode::Help => self.handle_help_key(key),
+            AppMode::Filtering => self.handle_filtering_key(key),
+            AppMode::Tasks => self.handle_tasks_key(key),
+            AppMode::AddingTask => self.handle_adding_task_key(key),
+            AppMode::EditingSection => Ok(()),
+            AppMode::History => self.handle_history_key(key),
+            AppMode::Log => self.handle_log_key(key),
+            AppMode::Changes => self.handle_changes_key(key),
+            AppMode::ConfirmDiscard => self.handle_discard_key(key),
+            AppMode::Committing => self.handle_committing_key(key),
+            AppMode::Stashes => self.handle_stash_key(key),
+            AppMode::ConfirmAbort => self.handle_abort_key(key),
         }
     }
 
@@ -156,6 +321,32 @@ impl App {
                 self.toggle_detail_view();
                 return Ok(());
             }
+            KeyCode::PageDown => {
+                self.scroll_detail(self.detail_page.get() as i32);
+                return Ok(());
+            }
+            KeyCode::PageUp => {
+                self.scroll_detail(-(self.detail_page.get() as i32));
+                return Ok(());
+            }
+            KeyCode::Char(' ') => {
+                self.toggle_group();
+                return Ok(());
+            }
+            // Unless a shortcut is configured on the digit
+            KeyCode::Char(c @ '1'..='9') if self.config.get_shortcut(&c.to_string()).is_none() => {
+                self.jump_to_number(c as usize - '1' as usize);
+                return Ok(());
+            }
+            KeyCode::Char('/') => {
+                self.mode = AppMode::Filtering;
+                self.filter_cursor = self.filter_input.chars().count();
+                return Ok(());
+            }
+            KeyCode::Esc if !self.filter.is_empty() => {
+                self.clear_filter();
+                return Ok(());
+            }
             _ => {}
         }
 
@@ -179,17 +370,42 @@ impl App {
     }
 
     fn run_builtin_action(&mut self, action: &str) -> Result<()> {
+        let needs_checkout = matches!(
+            action,
+            "edit" | "tasks" | "log" | "diff" | "edit_purpose" | "edit_notes" | "merge_main"
+                | "continue_operation" | "abort_operation" | "push" | "set_home"
+        );
+        if needs_checkout && self.selected_worktree().is_some_and(|wt| wt.is_bare) {
+            self.error = Some("The bare repository has no checkout, pick a worktree".to_string());
+            return Ok(());
+        }
+
         match action {
             "quit" => self.should_quit = true,
             "create" => self.start_create(),
             "delete" => self.start_delete(),
             "edit" => self.open_editor()?,
+            "tasks" => self.start_tasks(),
+            "log" => self.start_log(),
+            "diff" => self.start_changes(),
+            "stashes" => self.start_stashes(),
+            "edit_purpose" => self.start_section_edit("Purpose"),
+            "edit_notes" => self.start_section_edit("Notes"),
             "merge_main" => self.merge_main()?,
+            "continue_operation" => self.continue_operation(),
+            "abort_operation" => self.start_abort(),
+            "push" => self.push(),
             "toggle_view" => self.toggle_detail_view(),
+            "sort" => self.cycle_sort(),
+            "group" => self.toggle_grouping(),
+            "dashboard" => self.toggle_dashboard(),
+            "set_home" => self.set_home(),
             "refresh" => {
                 self.refresh_worktrees();
                 self.refresh_branches();
             }
+            "report" => self.copy_report(),
+            "history" => self.start_history(),
             "help" => self.mode = AppMode::Help,
             "cd" => self.exit_to_worktree(),
             _ => {
@@ -200,13 +416,13 @@ impl App {
     }
 
     fn run_command(&mut self, cmd: &str, mode: CommandMode) -> Result<()> {
-        let Some(wt) = self.worktrees.get(self.selected) else {
+        let Some(wt) = self.selected_worktree() else {
             return Ok(());
         };
 
         let branch = wt.branch.as_deref().unwrap_or("detached");
         let path = wt.path.to_string_lossy();
-        let repo_path = self.repo_path.to_string_lossy();
+        let repo_path = wt.repo.to_string_lossy();
 
         // Expand variables in command
         let expanded_cmd = cmd
@@ -219,7 +435,7 @@ impl App {
         match mode {
             CommandMode::Replace => {
                 // Take over terminal (like lazygit)
-                ratatui::restore();
+                ui::restore_terminal();
 
                 let status = std::process::Command::new("sh")
                     .arg("-c")
@@ -227,7 +443,7 @@ impl App {
                     .current_dir(&wt.path)
                     .status();
 
-                let _ = ratatui::init();
+                let _ = ui::init_terminal();
 
                 if let Err(e) = status {
                     self.error = Some(format!("Command failed: {}", e));
@@ -262,27 +478,34 @@ impl App {
                 self.input_cursor = 0;
                 self.filtered_branches.clear();
             }
-            KeyCode::Enter => {
-                if !self.input.is_empty() {
-                    self.create_worktree()?;
-                }
+            KeyCode::Enter if !self.input.is_empty() => {
+                self.create_worktree()?;
             }
-            KeyCode::Backspace => {
-                if self.input_cursor > 0 {
-                    self.input_cursor -= 1;
-                    self.input.remove(self.input_cursor);
-                    self.update_filtered_branches();
-                }
+            KeyCode::Backspace if self.input_cursor > 0 => {
+                self.input_cursor -= 1;
+                self.input.remove(self.input_cursor);
+                self.update_filtered_branches();
             }
-            KeyCode::Left => {
-                if self.input_cursor > 0 {
-                    self.input_cursor -= 1;
-                }
+            KeyCode::Left if self.input_cursor > 0 => {
+                self.input_cursor -= 1;
             }
-            KeyCode::Right => {
-                if self.input_cursor < self.input.len() {
-                    self.input_cursor += 1;
-                }
+            KeyCode::Right if self.input_cursor < self.input.len() => {
+                self.input_cursor += 1;
+            }
+            // Cycle through templates, wrapping back to automatic selection
+            KeyCode::Down => {
+                self.template_choice = match self.template_choice {
+                    None if !self.templates.is_empty() => Some(0),
+                    Some(i) if i + 1 < self.templates.len() => Some(i + 1),
+                    _ => None,
+                };
+            }
+            KeyCode::Up => {
+                self.template_choice = match self.template_choice {
+                    None => self.templates.len().checked_sub(1),
+                    Some(0) => None,
+                    Some(i) => Some(i - 1),
+                };
             }
             KeyCode::Tab => {
                 // Autocomplete from filtered branches
@@ -326,50 +549,437 @@ impl App {
         Ok(())
     }
 
+    fn handle_filtering_key(&mut self, key: KeyCode) -> Result<()> {
+        match key {
+            KeyCode::Esc => {
+                self.clear_filter();
+                self.mode = AppMode::Normal;
+            }
+            KeyCode::Enter => {
+                self.mode = AppMode::Normal;
+            }
+            KeyCode::Down => self.select_next(),
+            KeyCode::Up => self.select_previous(),
+            key => {
+                if crate::editor::edit_line(&mut self.filter_input, &mut self.filter_cursor, key) {
+                    self.apply_filter();
+                }
+            }
+        }
+        Ok(())
+    }
+
     fn select_next(&mut self) {
-        if self.worktrees.is_empty() {
-            return;
+        let cursor = self.cursor();
+        if cursor + 1 < self.rows.len() {
+            self.move_cursor(cursor + 1);
         }
-        self.selected = (self.selected + 1).min(self.worktrees.len() - 1);
-        self.list_state.select(Some(self.selected));
-        self.load_status_content();
     }
 
     fn select_previous(&mut self) {
-        if self.worktrees.is_empty() {
-            return;
+        let cursor = self.cursor();
+        if cursor > 0 {
+            self.move_cursor(cursor - 1);
+        }
+    }
+
+    /// Row index of the highlighted list entry (worktree or group header)
+    fn cursor(&self) -> usize {
+        self.list_state.selected().unwrap_or(0)
+    }
+
+    fn move_cursor(&mut self, row: usize) {
+        self.list_state.select(Some(row));
+        if let Some(ListRow::Worktree(index)) = self.rows.get(row) {
+            if self.selected != *index {
+                self.detail_scroll = 0;
+            }
+            self.selected = *index;
         }
-        self.selected = self.selected.saturating_sub(1);
-        self.list_state.select(Some(self.selected));
         self.load_status_content();
     }
 
+    fn scroll_detail(&mut self, delta: i32) {
+        let max = self.detail_max_scroll.get() as i32;
+        self.detail_scroll = (self.detail_scroll as i32 + delta).clamp(0, max) as u16;
+    }
+
+    /// Re-evaluate filter and sort order, keeping the selection on the same
+    /// worktree when it is still visible and moving it to the first match otherwise
+    fn apply_filter(&mut self) {
+        self.filter = Filter::parse(&self.filter_input);
+        self.visible = self
+            .worktrees
+            .iter()
+            .enumerate()
+            .filter(|(_, wt)| self.filter.matches(wt))
+            .map(|(i, _)| i)
+            .collect();
+        crate::sort::sort_indices(&mut self.visible, &self.worktrees, self.state.sort);
+
+        if !self.visible.contains(&self.selected) {
+            if let Some(&first) = self.visible.first() {
+                self.selected = first;
+            }
+        }
+        self.rebuild_rows();
+    }
+
+    /// Build display rows from the visible worktrees, inserting group headers
+    /// and hiding members of collapsed groups when grouping is enabled
+    fn rebuild_rows(&mut self) {
+        self.rows.clear();
+
+        if self.state.group_by_prefix || self.dashboard {
+            // Ungrouped worktrees first, then groups in order of their first member
+            let mut groups: Vec<(&str, Vec<usize>)> = Vec::new();
+            for &i in &self.visible {
+                match crate::sort::group_of(&self.worktrees[i], self.dashboard) {
+                    None => self.rows.push(ListRow::Worktree(i)),
+                    Some(name) => match groups.iter_mut().find(|(g, _)| *g == name) {
+                        Some((_, members)) => members.push(i),
+                        None => groups.push((name, vec![i])),
+                    },
+                }
+            }
+
+            for (name, members) in groups {
+                let collapsed = self.state.collapsed_groups.iter().any(|g| g == name);
+                self.rows.push(ListRow::Group {
+                    name: name.to_string(),
+                    count: members.len(),
+                    collapsed,
+                });
+                if !collapsed {
+                    self.rows.extend(members.into_iter().map(ListRow::Worktree));
+                }
+            }
+        } else {
+            self.rows.extend(self.visible.iter().copied().map(ListRow::Worktree));
+        }
+
+        // Point the cursor at the selected worktree, or its collapsed group header
+        let group = self
+            .worktrees
+            .get(self.selected)
+            .and_then(|wt| crate::sort::group_of(wt, self.dashboard));
+        let row = self
+            .rows
+            .iter()
+            .position(|r| *r == ListRow::Worktree(self.selected))
+            .or_else(|| {
+                self.rows.iter().position(
+                    |r| matches!(r, ListRow::Group { name, .. } if Some(name.as_str()) == group),
+                )
+            })
+            .unwrap_or(0);
+        self.move_cursor(row);
+    }
+
+    fn cycle_sort(&mut self) {
+        self.state.sort = self.state.sort.next();
+        self.save_state();
+        self.apply_filter();
+    }
+
+    fn toggle_grouping(&mut self) {
+        self.state.group_by_prefix = !self.state.group_by_prefix;
+        self.save_state();
+        self.rebuild_rows();
+    }
+
+    /// Collapse or expand the group under the cursor (or the selected worktree's group)
+    fn toggle_group(&mut self) {
+        let (name, on_header) = match self.rows.get(self.cursor()) {
+            Some(ListRow::Group { name, .. }) => (name.clone(), true),
+            Some(ListRow::Worktree(i)) => match crate::sort::group_of(&self.worktrees[*i], self.dashboard) {
+                Some(name) => (name.to_string(), false),
+                None => return,
+            },
+            None => return,
+        };
+
+        if let Some(pos) = self.state.collapsed_groups.iter().position(|g| *g == name) {
+            self.state.collapsed_groups.remove(pos);
+        } else {
+            self.state.collapsed_groups.push(name.clone());
+        }
+        self.save_state();
+        self.rebuild_rows();
+
+        // Stay on the header that was toggled rather than jumping to the selection
+        if on_header {
+            if let Some(row) = self
+                .rows
+                .iter()
+                .position(|r| matches!(r, ListRow::Group { name: n, .. } if *n == name))
+            {
+                self.move_cursor(row);
+            }
+        }
+    }
+
+    fn save_state(&mut self) {
+        if let Err(e) = self.state.save() {
+            self.error = Some(format!("Failed to save state: {}", e));
+        }
+    }
+
+    fn clear_filter(&mut self) {
+        self.filter_input.clear();
+        self.filter_cursor = 0;
+        self.apply_filter();
+    }
+
     fn start_create(&mut self) {
         self.mode = AppMode::Creating;
         self.input.clear();
         self.input_cursor = 0;
+        self.templates = crate::status::list_templates(&self.checkout(), &self.config);
+        self.template_choice = None;
+        if self.dashboard {
+            self.refresh_branches();
+        }
         self.update_filtered_branches();
     }
 
     fn start_delete(&mut self) {
-        if let Some(wt) = self.worktrees.get(self.selected) {
+        if let Some(wt) = self.selected_worktree() {
             if wt.is_main {
                 self.error = Some("Cannot delete main worktree".to_string());
                 return;
             }
+            if wt.is_bare {
+                self.error = Some("Cannot delete the bare repository".to_string());
+                return;
+            }
+            if let Some(op) = wt.operation {
+                self.error = Some(format!(
+                    "A {} is in progress here; continue or abort it before deleting",
+                    op.command()
+                ));
+                return;
+            }
             self.mode = AppMode::ConfirmDelete;
         }
     }
 
     fn refresh_worktrees(&mut self) {
-        match crate::git::list_worktrees(&self.repo_path) {
-            Ok(worktrees) => {
-                self.worktrees = worktrees;
-                if self.selected >= self.worktrees.len() {
-                    self.selected = self.worktrees.len().saturating_sub(1);
+        // A full listing supersedes a revalidation still running
+        self.revalidating = None;
+        let mut worktrees = Vec::new();
+        let mut listed_any = false;
+        for repo in self.repos() {
+            match crate::git::list_worktrees(&repo, self.config.notes_storage) {
+                Ok(listed) => {
+                    worktrees.extend(listed);
+                    listed_any = true;
+                }
+                Err(e) => {
+                    self.error = Some(format!("Failed to list worktrees in {}: {}", repo.display(), e));
+                }
+            }
+        }
+        // Keep showing what we had if git failed everywhere
+        if listed_any {
+            self.set_worktrees(worktrees);
+        }
+    }
+
+    /// Repositories whose worktrees are listed
+    fn repos(&self) -> Vec<PathBuf> {
+        if self.dashboard {
+            self.config.dashboard_repos()
+        } else {
+            vec![self.repo_path.clone()]
+        }
+    }
+
+    /// Repository of the highlighted row, which repo-wide actions (create,
+    /// stashes, history) work on. Outside the dashboard that's always the
+    /// launch repo.
+    fn repo(&self) -> PathBuf {
+        if !self.dashboard {
+            return self.repo_path.clone();
+        }
+        let wt = match self.rows.get(self.cursor()) {
+            Some(ListRow::Group { name, .. }) => self
+                .worktrees
+                .iter()
+                .find(|wt| crate::sort::repo_name(wt) == name),
+            _ => self.worktrees.get(self.selected),
+        };
+        wt.map_or_else(|| self.repo_path.clone(), |wt| wt.repo.clone())
+    }
+
+    /// Checkout of the highlighted row's repository, where `.wtm/templates`
+    /// and `.worktree-init.sh` are looked up: the home worktree, which in a
+    /// bare repository isn't the repository itself
+    fn checkout(&self) -> PathBuf {
+        let repo = self.repo();
+        self.worktrees
+            .iter()
+            .find(|wt| wt.is_main && (wt.repo == repo || wt.path == repo))
+            .map_or(repo, |wt| wt.path.clone())
+    }
+
+    /// Make the selected worktree the one listed first and protected from delete
+    fn set_home(&mut self) {
+        let Some(wt) = self.selected_worktree() else {
+            return;
+        };
+        if !self.worktrees.iter().any(|w| w.is_bare && w.repo == wt.repo) {
+            self.error = Some("The main checkout is always home outside bare repositories".to_string());
+            return;
+        }
+        let name = wt.branch.clone().unwrap_or_else(|| wt.path.display().to_string());
+        match crate::git::set_home_worktree(&wt.repo, &wt.path) {
+            Ok(()) => {
+                self.notice = Some(format!("{} is now the home worktree", name));
+                self.refresh_worktrees();
+            }
+            Err(e) => self.error = Some(format!("Failed to set home worktree: {}", e)),
+        }
+    }
+
+    /// Point out status files left in worktrees after switching `notes_storage`.
+    /// Moving them is left to `wtm migrate-notes`, they may be tracked in git.
+    fn check_status_migration(&mut self) {
+        let notes = self.config.notes_storage;
+        if notes == NotesStorage::Worktree {
+            return;
+        }
+        let count = self
+            .worktrees
+            .iter()
+            .filter(|wt| {
+                let status_path = crate::status::status_path(notes, &wt.path, wt.branch.as_deref());
+                crate::status::needs_migration(&wt.path, &status_path)
+            })
+            .count();
+        if count > 0 {
+            self.notice = Some(format!(
+                "{} worktree(s) still have {}, run `wtm migrate-notes` to move them",
+                count,
+                crate::status::STATUS_FILE
+            ));
+        }
+    }
+
+    /// Switch between the launch repo and all configured repos
+    fn toggle_dashboard(&mut self) {
+        if !self.dashboard && self.config.dashboard_repos().is_empty() {
+            self.error = Some("No repos configured, set repos or repos_root in the config".to_string());
+            return;
+        }
+        self.save_cache();
+        self.dashboard = !self.dashboard;
+        self.load_cached_worktrees();
+    }
+
+    fn set_worktrees(&mut self, mut worktrees: Vec<Worktree>) {
+        self.state.apply_history(&mut worktrees);
+        if let Some(watcher) = &mut self.watcher {
+            watcher.watch(&worktrees, self.config.notes_storage);
+        }
+        // The new list may be ordered differently, keep the same worktree selected
+        let selected_path = self.worktrees.get(self.selected).map(|wt| wt.path.clone());
+        self.worktrees = worktrees;
+        match selected_path.and_then(|path| self.worktrees.iter().position(|wt| wt.path == path)) {
+            Some(index) => self.selected = index,
+            None => self.selected = self.selected.min(self.worktrees.len().saturating_sub(1)),
+        }
+        self.apply_filter();
+    }
+
+    /// A dialog or detail view (tasks, changes, log, inline edit) works on the
+    /// selected worktree, so background and live refreshes wait until the user
+    /// is back in the list; changes seen meanwhile are applied then
+    fn is_using_selection(&self) -> bool {
+        !matches!(
+            self.mode,
+            AppMode::Normal | AppMode::Filtering | AppMode::Help | AppMode::Creating | AppMode::History
+        )
+    }
+
+    /// Show the worktrees from the last run right away, and re-check them
+    /// against git in the background
+    fn load_cached_worktrees(&mut self) {
+        let caches: Vec<(PathBuf, Cache)> = self
+            .repos()
+            .into_iter()
+            .map(|repo| {
+                let cache = Cache::load(&repo);
+                (repo, cache)
+            })
+            .collect();
+        if caches.iter().any(|(_, cache)| cache.is_empty()) {
+            self.refresh_worktrees();
+            return;
+        }
+
+        self.set_worktrees(caches.iter().flat_map(|(_, cache)| cache.worktrees()).collect());
+        let (tx, rx) = channel();
+        let notes = self.config.notes_storage;
+        std::thread::spawn(move || {
+            let mut worktrees = Vec::new();
+            for (repo, cache) in caches {
+                match cache.revalidate(&repo, notes) {
+                    Ok(listed) => worktrees.extend(listed),
+                    Err(e) => {
+                        let _ = tx.send(Err(e));
+                        return;
+                    }
+                }
+            }
+            let _ = tx.send(Ok(worktrees));
+        });
+        self.revalidating = Some(rx);
+        self.updated_while_revalidating.clear();
+    }
+
+    /// Remember the worktrees of each listed repo for the next start
+    fn save_cache(&self) {
+        let notes = self.config.notes_storage;
+        let mut repos: Vec<&PathBuf> = self.worktrees.iter().map(|wt| &wt.repo).collect();
+        repos.dedup();
+        for repo in repos {
+            let worktrees: Vec<_> = self.worktrees.iter().filter(|wt| wt.repo == *repo).cloned().collect();
+            let _ = Cache::save(repo, &worktrees, notes);
+        }
+    }
+
+    /// Swap in the revalidated worktrees once the background check is done
+    fn finish_revalidation(&mut self) {
+        let Some(rx) = &self.revalidating else {
+            return;
+        };
+        let result = match rx.try_recv() {
+            Ok(result) => result,
+            Err(TryRecvError::Empty) => return,
+            // The check died without an answer, list them the slow way
+            Err(TryRecvError::Disconnected) => {
+                self.refresh_worktrees();
+                return;
+            }
+        };
+        self.revalidating = None;
+        match result {
+            Ok(mut worktrees) => {
+                // The check read these before the user or a live reload changed them
+                for wt in &mut worktrees {
+                    if !self.updated_while_revalidating.contains(&wt.path) {
+                        continue;
+                    }
+                    if let Some(current) = self.worktrees.iter().find(|c| c.path == wt.path) {
+                        *wt = current.clone();
+                    }
+                }
+                self.updated_while_revalidating.clear();
+                self.set_worktrees(worktrees);
+                if self.mode == AppMode::Normal {
+                    self.load_status_content();
                 }
-                self.list_state.select(Some(self.selected));
-                self.load_status_content();
             }
             Err(e) => {
                 self.error = Some(format!("Failed to list worktrees: {}", e));
@@ -377,8 +987,46 @@ impl App {
         }
     }
 
+    /// Reload just the worktrees whose HEAD, index or status file changed on disk
+    fn reload_changed_worktrees(&mut self) {
+        let Some(watcher) = &mut self.watcher else {
+            return;
+        };
+        let changed = watcher.changed();
+        if changed.is_empty() {
+            return;
+        }
+
+        let mut selected_changed = false;
+        for path in changed {
+            let Some(index) = self.worktrees.iter().position(|wt| wt.path == path) else {
+                continue;
+            };
+            match crate::git::reload_worktree(&self.worktrees[index], self.config.notes_storage) {
+                Ok(mut wt) => {
+                    self.state.apply_history(std::slice::from_mut(&mut wt));
+                    self.worktrees[index] = wt;
+                    if self.revalidating.is_some() {
+                        self.updated_while_revalidating.insert(path.clone());
+                    }
+                }
+                // Removed behind our back, e.g. `git worktree remove` elsewhere
+                Err(_) => {
+                    self.refresh_worktrees();
+                    return;
+                }
+            }
+            selected_changed |= self.selected == index;
+        }
+
+        self.apply_filter();
+        if selected_changed && self.mode == AppMode::Normal {
+            self.load_status_content();
+        }
+    }
+
     fn refresh_branches(&mut self) {
-        match crate::git::list_branches(&self.repo_path) {
+        match crate::git::list_branches(&self.repo()) {
             Ok(branches) => {
                 self.branches = branches;
             }
@@ -403,10 +1051,10 @@ impl App {
     }
 
     fn load_status_content(&mut self) {
-        if let Some(wt) = self.worktrees.get(self.selected) {
+        if let Some(wt) = self.selected_worktree().filter(|wt| !wt.is_bare) {
             match self.detail_view {
                 DetailViewMode::Notes => {
-                    let status_path = wt.path.join(".worktree-status.md");
+                    let status_path = self.status_path(wt);
                     if status_path.exists() {
                         self.status_content = std::fs::read_to_string(&status_path).ok();
                     } else {
@@ -414,7 +1062,28 @@ impl App {
                     }
                 }
                 DetailViewMode::GitStatus => {
-                    self.status_content = crate::git::get_git_status(&wt.path).ok();
+                    let files = crate::git::changed_files(&wt.path);
+                    self.status_content = None;
+                    match files {
+                        Ok(files) => self.changed_files = files,
+                        Err(e) => {
+                            self.changed_files.clear();
+                            self.error = Some(e.to_string());
+                        }
+                    }
+                    self.file_cursor = self.file_cursor.min(self.changed_files.len().saturating_sub(1));
+                }
+                DetailViewMode::Log => {
+                    let log = crate::git::branch_log(&wt.path, wt.branch.as_deref());
+                    self.status_content = None;
+                    match log {
+                        Ok(commits) => self.commits = commits,
+                        Err(e) => {
+                            self.commits.clear();
+                            self.error = Some(e.to_string());
+                        }
+                    }
+                    self.log_cursor = self.log_cursor.min(self.commits.len().saturating_sub(1));
                 }
             }
         } else {
@@ -423,9 +1092,11 @@ impl App {
     }
 
     fn toggle_detail_view(&mut self) {
+        self.detail_scroll = 0;
         self.detail_view = match self.detail_view {
             DetailViewMode::Notes => DetailViewMode::GitStatus,
-            DetailViewMode::GitStatus => DetailViewMode::Notes,
+            DetailViewMode::GitStatus => DetailViewMode::Log,
+            DetailViewMode::Log => DetailViewMode::Notes,
         };
         self.load_status_content();
     }
@@ -440,26 +1111,17 @@ impl App {
         let branch_exists = self.branches.contains(&branch);
 
         // Generate worktree path
-        let repo_name = self
-            .repo_path
-            .file_name()
-            .and_then(|n| n.to_str())
-            .unwrap_or("repo");
-        let worktree_path = self
-            .repo_path
-            .parent()
-            .unwrap_or(&self.repo_path)
-            .join(format!("{}-{}", repo_name, branch.replace('/', "-")));
+        let repo_path = self.repo();
+        let worktree_path = crate::git::new_worktree_path(&repo_path, &branch);
 
         // Get start point from selected worktree (for new branches)
         let start_point = self
-            .worktrees
-            .get(self.selected)
+            .selected_worktree()
             .and_then(|wt| wt.branch.clone());
 
         // Create worktree
         match crate::git::create_worktree(
-            &self.repo_path,
+            &repo_path,
             &branch,
             &worktree_path,
             branch_exists,
@@ -467,12 +1129,24 @@ impl App {
         ) {
             Ok(()) => {
                 // Generate status file
-                let status_content = crate::status::generate_status_file(&branch);
-                let status_path = worktree_path.join(".worktree-status.md");
+                let (template, _) = self.create_template();
+                let base_branch = match start_point.as_deref() {
+                    Some(start) if !branch_exists => start.to_string(),
+                    _ => Self::main_branch(&repo_path),
+                };
+                let status_content = self.status_file_for(&branch, &base_branch, Some(&template));
+                let status_path = crate::status::status_path(
+                    self.config.notes_storage,
+                    &worktree_path,
+                    Some(&branch),
+                );
+                if let Some(parent) = status_path.parent() {
+                    let _ = std::fs::create_dir_all(parent);
+                }
                 let _ = std::fs::write(&status_path, status_content);
 
                 // Run init script if exists
-                let init_script = self.repo_path.join(".worktree-init.sh");
+                let init_script = self.checkout().join(".worktree-init.sh");
                 if init_script.exists() {
                     let _ = std::process::Command::new("sh")
                         .arg(&init_script)
@@ -498,16 +1172,24 @@ impl App {
     }
 
     fn delete_worktree(&mut self) -> Result<()> {
-        if let Some(wt) = self.worktrees.get(self.selected) {
+        if let Some(wt) = self.selected_worktree() {
             if wt.is_main {
                 self.error = Some("Cannot delete main worktree".to_string());
                 self.mode = AppMode::Normal;
                 return Ok(());
             }
 
+            // Read the notes first, with git_dir storage they go with the worktree
+            let notes = std::fs::read_to_string(self.status_path(wt)).ok();
+            let entry = ArchiveEntry::new(wt, notes);
+
             let path = wt.path.clone();
-            match crate::git::delete_worktree(&self.repo_path, &path, wt.has_changes) {
+            let repo_path = wt.repo.clone();
+            match crate::git::delete_worktree(&repo_path, &path, wt.has_changes) {
                 Ok(()) => {
+                    if let Err(e) = Archive::append(&repo_path, entry) {
+                        self.error = Some(format!("Deleted, but failed to archive notes: {}", e));
+                    }
                     self.mode = AppMode::Normal;
                     self.refresh_worktrees();
                 }
@@ -522,21 +1204,14 @@ impl App {
     }
 
     fn open_editor(&mut self) -> Result<()> {
-        if let Some(wt) = self.worktrees.get(self.selected) {
-            let status_path = wt.path.join(".worktree-status.md");
-
-            // Create status file if it doesn't exist
-            if !status_path.exists() {
-                let branch = wt.branch.as_deref().unwrap_or("unknown");
-                let content = crate::status::generate_status_file(branch);
-                std::fs::write(&status_path, content)?;
-            }
+        if let Some(wt) = self.selected_worktree() {
+            let status_path = self.ensure_status_file(wt)?;
 
             // Get editor from environment
             let editor = std::env::var("EDITOR").unwrap_or_else(|_| "vim".to_string());
 
             // Restore terminal for editor
-            ratatui::restore();
+            ui::restore_terminal();
 
             // Run editor
             let status = std::process::Command::new(&editor)
@@ -544,7 +1219,7 @@ impl App {
                 .status();
 
             // Reinitialize terminal
-            let _ = ratatui::init();
+            let _ = ui::init_terminal();
 
             if let Err(e) = status {
                 self.error = Some(format!("Failed to open editor: {}", e));
@@ -558,35 +1233,799 @@ impl App {
         Ok(())
     }
 
-    fn merge_main(&mut self) -> Result<()> {
-        if let Some(wt) = self.worktrees.get(self.selected) {
-            if wt.is_main {
-                self.error = Some("Cannot merge main into itself".to_string());
-                return Ok(());
+    fn handle_tasks_key(&mut self, key: KeyCode) -> Result<()> {
+        let task_count = self
+            .status_content
+            .as_deref()
+            .map_or(0, |c| crate::status::find_tasks(c).len());
+
+        match key {
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('h') | KeyCode::Left => {
+                self.mode = AppMode::Normal;
             }
+            KeyCode::Char('j') | KeyCode::Down if self.task_cursor + 1 < task_count => {
+                self.task_cursor += 1;
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.task_cursor = self.task_cursor.saturating_sub(1);
+            }
+            KeyCode::Char(' ') | KeyCode::Char('x') => self.toggle_selected_task(),
+            KeyCode::Char('a') => {
+                self.input.clear();
+                self.input_cursor = 0;
+                self.mode = AppMode::AddingTask;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
 
-            match crate::git::merge_main_ff(&wt.path) {
-                Ok(()) => {
-                    self.refresh_worktrees();
-                    self.load_status_content();
+    fn handle_log_key(&mut self, key: KeyCode) -> Result<()> {
+        // Viewing a commit: scroll it, Esc goes back to the list
+        if self.commit_diff.is_some() {
+            match key {
+                KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('h') | KeyCode::Left => {
+                    self.commit_diff = None;
+                    self.detail_scroll = 0;
                 }
-                Err(e) => {
-                    self.error = Some(e.to_string());
+                KeyCode::Char('j') | KeyCode::Down => self.scroll_detail(1),
+                KeyCode::Char('k') | KeyCode::Up => self.scroll_detail(-1),
+                KeyCode::PageDown | KeyCode::Char(' ') => {
+                    self.scroll_detail(self.detail_page.get() as i32)
                 }
+                KeyCode::PageUp => self.scroll_detail(-(self.detail_page.get() as i32)),
+                KeyCode::Char('n') => self.jump_to_hunk(true),
+                KeyCode::Char('N') => self.jump_to_hunk(false),
+                _ => {}
             }
+            return Ok(());
         }
 
+        match key {
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('h') | KeyCode::Left => {
+                self.mode = AppMode::Normal;
+            }
+            KeyCode::Char('j') | KeyCode::Down if self.log_cursor + 1 < self.commits.len() => {
+                self.log_cursor += 1;
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.log_cursor = self.log_cursor.saturating_sub(1);
+            }
+            KeyCode::Enter | KeyCode::Char('l') | KeyCode::Right => self.open_commit(),
+            _ => {}
+        }
         Ok(())
     }
 
-    fn exit_to_worktree(&mut self) {
-        if let Some(wt) = self.worktrees.get(self.selected) {
-            self.exit_path = Some(wt.path.clone());
-            self.should_quit = true;
+    fn handle_changes_key(&mut self, key: KeyCode) -> Result<()> {
+        // Viewing a diff: scroll, jump between hunks, toggle word diff
+        if self.file_diff.is_some() {
+            match key {
+                KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('h') | KeyCode::Left => {
+                    self.file_diff = None;
+                    self.diff_target = None;
+                    self.detail_scroll = 0;
+                }
+                KeyCode::Char('j') | KeyCode::Down => self.scroll_detail(1),
+                KeyCode::Char('k') | KeyCode::Up => self.scroll_detail(-1),
+                KeyCode::PageDown | KeyCode::Char(' ') => {
+                    self.scroll_detail(self.detail_page.get() as i32)
+                }
+                KeyCode::PageUp => self.scroll_detail(-(self.detail_page.get() as i32)),
+                KeyCode::Char('n') => self.jump_to_hunk(true),
+                KeyCode::Char('N') => self.jump_to_hunk(false),
+                KeyCode::Char('w') => {
+                    self.word_diff = !self.word_diff;
+                    self.load_diff();
+                }
+                _ => {}
+            }
+            return Ok(());
         }
-    }
 
-    pub fn selected_worktree(&self) -> Option<&Worktree> {
-        self.worktrees.get(self.selected)
+        match key {
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('h') | KeyCode::Left => {
+                self.mode = AppMode::Normal;
+                self.load_status_content();
+            }
+            KeyCode::Char('j') | KeyCode::Down if self.file_cursor + 1 < self.changed_files.len() => {
+                self.file_cursor += 1;
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.file_cursor = self.file_cursor.saturating_sub(1);
+            }
+            KeyCode::Enter | KeyCode::Char('l') | KeyCode::Right => {
+                if let Some(file) = self.changed_files.get(self.file_cursor) {
+                    self.diff_target = Some(DiffTarget::File(file.clone()));
+                    self.load_diff();
+                }
+            }
+            KeyCode::Char('b') => {
+                self.diff_target = Some(DiffTarget::Branch);
+                self.load_diff();
+            }
+            KeyCode::Char(' ') => self.toggle_staged(),
+            KeyCode::Char('x') if !self.changed_files.is_empty() => {
+                self.mode = AppMode::ConfirmDiscard;
+            }
+            KeyCode::Char('c') => {
+                if !self.changed_files.iter().any(ChangedFile::is_staged) {
+                    self.error = Some("Nothing staged (space stages the selected file)".to_string());
+                    return Ok(());
+                }
+                self.input.clear();
+                self.input_cursor = 0;
+                self.mode = AppMode::Committing;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn handle_discard_key(&mut self, key: KeyCode) -> Result<()> {
+        match key {
+            KeyCode::Char('y') | KeyCode::Char('Y') => {
+                self.mode = AppMode::Changes;
+                let Some(wt) = self.selected_worktree() else {
+                    return Ok(());
+                };
+                if let Some(file) = self.changed_files.get(self.file_cursor) {
+                    if let Err(e) = crate::git::discard_file(&wt.path, file) {
+                        self.error = Some(e.to_string());
+                    }
+                }
+                self.refresh_changes();
+            }
+            KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                self.mode = AppMode::Changes;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn handle_committing_key(&mut self, key: KeyCode) -> Result<()> {
+        match key {
+            KeyCode::Esc => {
+                self.input.clear();
+                self.mode = AppMode::Changes;
+            }
+            KeyCode::Enter => {
+                if self.input.trim().is_empty() {
+                    return Ok(());
+                }
+                let Some(wt) = self.selected_worktree() else {
+                    return Ok(());
+                };
+                match crate::git::commit(&wt.path, self.input.trim()) {
+                    Ok(()) => {
+                        self.notice = Some("Committed".to_string());
+                        self.input.clear();
+                        self.input_cursor = 0;
+                    }
+                    Err(e) => self.error = Some(e.to_string()),
+                }
+                self.mode = AppMode::Changes;
+                self.refresh_changes();
+            }
+            key => {
+                crate::editor::edit_line(&mut self.input, &mut self.input_cursor, key);
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_adding_task_key(&mut self, key: KeyCode) -> Result<()> {
+        match key {
+            KeyCode::Esc => {
+                self.input.clear();
+                self.mode = AppMode::Tasks;
+            }
+            KeyCode::Enter => {
+                if !self.input.trim().is_empty() {
+                    if let Some(content) = self.status_content.clone() {
+                        let updated = crate::status::append_task(&content, &self.input);
+                        self.write_status_content(updated);
+                        // Move the cursor onto the new task
+                        if let Some(content) = &self.status_content {
+                            let tasks = crate::status::find_tasks(content);
+                            if let Some(pos) = tasks.iter().position(|t| t.text == self.input.trim()) {
+                                self.task_cursor = pos;
+                            }
+                        }
+                    }
+                }
+                self.input.clear();
+                self.input_cursor = 0;
+                self.mode = AppMode::Tasks;
+            }
+            key => {
+                crate::editor::edit_line(&mut self.input, &mut self.input_cursor, key);
+            }
+        }
+        Ok(())
+    }
+
+    /// Browse the notes of deleted worktrees
+    fn start_history(&mut self) {
+        match Archive::load(&self.repo()) {
+            Ok(archive) => {
+                self.archive = archive;
+                self.history_query.clear();
+                self.history_cursor = 0;
+                self.history_scroll = 0;
+                self.mode = AppMode::History;
+            }
+            Err(e) => {
+                self.error = Some(format!("Failed to load archive: {}", e));
+            }
+        }
+    }
+
+    pub fn history_matches(&self) -> Vec<&ArchiveEntry> {
+        self.archive.search(&self.history_query)
+    }
+
+    fn handle_history_key(&mut self, key: KeyCode) -> Result<()> {
+        let count = self.history_matches().len();
+        match key {
+            KeyCode::Esc => self.mode = AppMode::Normal,
+            KeyCode::Down if self.history_cursor + 1 < count => {
+                self.history_cursor += 1;
+                self.history_scroll = 0;
+            }
+            KeyCode::Up if self.history_cursor > 0 => {
+                self.history_cursor -= 1;
+                self.history_scroll = 0;
+            }
+            KeyCode::PageDown => self.history_scroll = self.history_scroll.saturating_add(10),
+            KeyCode::PageUp => self.history_scroll = self.history_scroll.saturating_sub(10),
+            KeyCode::Backspace => {
+                self.history_query.pop();
+                self.history_cursor = 0;
+            }
+            KeyCode::Char(c) => {
+                self.history_query.push(c);
+                self.history_cursor = 0;
+                self.history_scroll = 0;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn start_stashes(&mut self) {
+        if self.selected_worktree().is_none() {
+            return;
+        }
+        self.stash_cursor = 0;
+        self.confirm_drop = false;
+        self.reload_stashes();
+        self.mode = AppMode::Stashes;
+    }
+
+    /// Stashes shown in the stash view: those made on the selected branch, or all
+    pub fn visible_stashes(&self) -> Vec<&Stash> {
+        let branch = self.selected_worktree().and_then(|w| w.branch.as_deref());
+        self.stashes
+            .iter()
+            .filter(|s| self.stash_all || (branch.is_some() && s.branch.as_deref() == branch))
+            .collect()
+    }
+
+    fn reload_stashes(&mut self) {
+        self.stashes = crate::git::list_stashes(&self.repo());
+        let count = self.visible_stashes().len();
+        self.stash_cursor = self.stash_cursor.min(count.saturating_sub(1));
+        self.load_stash_diff();
+    }
+
+    fn load_stash_diff(&mut self) {
+        self.stash_scroll = 0;
+        let name = self.visible_stashes().get(self.stash_cursor).map(|s| s.name.clone());
+        self.stash_diff = name.and_then(|name| crate::git::stash_diff(&self.repo(), &name).ok());
+    }
+
+    fn handle_stash_key(&mut self, key: KeyCode) -> Result<()> {
+        let selected = self.visible_stashes().get(self.stash_cursor).map(|s| s.name.clone());
+
+        if self.confirm_drop {
+            self.confirm_drop = false;
+            if let (KeyCode::Char('y') | KeyCode::Char('Y'), Some(name)) = (key, &selected) {
+                self.run_stash_action(|path| crate::git::stash_drop(path, name));
+            }
+            return Ok(());
+        }
+
+        let count = self.visible_stashes().len();
+        match key {
+            KeyCode::Esc | KeyCode::Char('q') => self.mode = AppMode::Normal,
+            KeyCode::Char('j') | KeyCode::Down if self.stash_cursor + 1 < count => {
+                self.stash_cursor += 1;
+                self.load_stash_diff();
+            }
+            KeyCode::Char('k') | KeyCode::Up if self.stash_cursor > 0 => {
+                self.stash_cursor -= 1;
+                self.load_stash_diff();
+            }
+            KeyCode::PageDown => self.stash_scroll = self.stash_scroll.saturating_add(10),
+            KeyCode::PageUp => self.stash_scroll = self.stash_scroll.saturating_sub(10),
+            KeyCode::Tab => {
+                self.stash_all = !self.stash_all;
+                self.stash_cursor = 0;
+                self.load_stash_diff();
+            }
+            KeyCode::Char('s') => self.run_stash_action(crate::git::stash_push),
+            KeyCode::Char('p') | KeyCode::Char('a') => {
+                if let Some(name) = &selected {
+                    let pop = key == KeyCode::Char('p');
+                    self.run_stash_action(|path| crate::git::stash_apply(path, name, pop));
+                }
+            }
+            KeyCode::Char('d') if selected.is_some() => self.confirm_drop = true,
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Run a stash command in the selected worktree, then refresh the stash
+    /// view and the list's dirty and stash markers
+    fn run_stash_action(&mut self, action: impl FnOnce(&std::path::Path) -> Result<()>) {
+        let Some(wt) = self.selected_worktree() else {
+            return;
+        };
+        if let Err(e) = action(&wt.path) {
+            self.error = Some(e.to_string());
+        }
+        self.refresh_worktrees();
+        self.reload_stashes();
+    }
+
+    /// Edit a status file section inline in the detail pane
+    fn start_section_edit(&mut self, section: &'static str) {
+        let Some(wt) = self.selected_worktree() else {
+            return;
+        };
+        if let Err(e) = self.ensure_status_file(wt) {
+            self.error = Some(format!("Failed to create status file: {}", e));
+            return;
+        }
+
+        self.detail_view = DetailViewMode::Notes;
+        self.load_status_content();
+        let body = self
+            .status_content
+            .as_deref()
+            .and_then(|c| crate::status::section_body(c, section))
+            .unwrap_or_default();
+        self.editor = TextEditor::new(&body);
+        self.editing_section = section;
+        self.mode = AppMode::EditingSection;
+    }
+
+    fn handle_editing_key(&mut self, key: KeyEvent) -> Result<()> {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            // Esc and Ctrl+S save, Ctrl+C throws the edit away
+            KeyCode::Esc => self.save_section_edit(),
+            KeyCode::Char('s') if ctrl => self.save_section_edit(),
+            KeyCode::Char('c') if ctrl => self.mode = AppMode::Normal,
+            _ => self.editor.input(key),
+        }
+        Ok(())
+    }
+
+    fn save_section_edit(&mut self) {
+        self.mode = AppMode::Normal;
+        let Some(content) = self.status_content.clone() else {
+            return;
+        };
+        let updated =
+            crate::status::replace_section(&content, self.editing_section, &self.editor.text());
+        if updated != content {
+            self.write_status_content(updated);
+        }
+    }
+
+    /// Focus the notes pane to check off tasks, creating the status file if needed
+    fn start_tasks(&mut self) {
+        let Some(wt) = self.selected_worktree() else {
+            return;
+        };
+        if let Err(e) = self.ensure_status_file(wt) {
+            self.error = Some(format!("Failed to create status file: {}", e));
+            return;
+        }
+
+        self.detail_view = DetailViewMode::Notes;
+        self.load_status_content();
+        self.task_cursor = 0;
+        self.mode = AppMode::Tasks;
+    }
+
+    fn start_log(&mut self) {
+        if self.selected_worktree().is_none() {
+            return;
+        }
+        self.detail_view = DetailViewMode::Log;
+        self.detail_scroll = 0;
+        self.log_cursor = 0;
+        self.commit_diff = None;
+        self.load_status_content();
+        self.mode = AppMode::Log;
+    }
+
+    fn start_changes(&mut self) {
+        let Some(wt) = self.selected_worktree() else {
+            return;
+        };
+        match crate::git::changed_files(&wt.path) {
+            Ok(files) => self.changed_files = files,
+            Err(e) => {
+                self.error = Some(e.to_string());
+                return;
+            }
+        }
+        self.detail_view = DetailViewMode::GitStatus;
+        self.detail_scroll = 0;
+        self.file_cursor = 0;
+        self.diff_target = None;
+        self.file_diff = None;
+        self.mode = AppMode::Changes;
+    }
+
+    fn toggle_staged(&mut self) {
+        let Some(wt) = self.selected_worktree() else {
+            return;
+        };
+        let Some(file) = self.changed_files.get(self.file_cursor) else {
+            return;
+        };
+        if let Err(e) = crate::git::toggle_staged(&wt.path, file) {
+            self.error = Some(e.to_string());
+        }
+        self.refresh_changes();
+    }
+
+    /// Reload the changed files after staging, discarding or committing, and
+    /// the worktree list for its dirty markers
+    fn refresh_changes(&mut self) {
+        self.refresh_worktrees();
+        let Some(wt) = self.selected_worktree() else {
+            return;
+        };
+        match crate::git::changed_files(&wt.path) {
+            Ok(files) => self.changed_files = files,
+            Err(e) => self.error = Some(e.to_string()),
+        }
+        self.file_cursor = self.file_cursor.min(self.changed_files.len().saturating_sub(1));
+    }
+
+    /// (Re)load the diff for `diff_target`, e.g. after toggling word diff
+    fn load_diff(&mut self) {
+        let Some(wt) = self.selected_worktree() else {
+            return;
+        };
+        let diff = match &self.diff_target {
+            Some(DiffTarget::File(file)) => crate::git::file_diff(&wt.path, file, self.word_diff),
+            Some(DiffTarget::Branch) => {
+                crate::git::branch_diff(&wt.path, wt.branch.as_deref(), self.word_diff)
+            }
+            None => return,
+        };
+        match diff {
+            Ok(diff) => {
+                self.file_diff = Some(diff);
+                self.detail_scroll = 0;
+            }
+            Err(e) => {
+                self.diff_target = None;
+                self.error = Some(e.to_string());
+            }
+        }
+    }
+
+    /// Scroll the open diff to the next or previous `@@` hunk. Diffs aren't
+    /// wrapped, so lines and scroll rows line up.
+    fn jump_to_hunk(&mut self, forward: bool) {
+        let diff = match self.mode {
+            AppMode::Log => self.commit_diff.as_deref(),
+            _ => self.file_diff.as_deref(),
+        };
+        let Some(diff) = diff else {
+            return;
+        };
+        let current = self.detail_scroll as usize;
+        let hunks = crate::git::hunk_starts(diff);
+        let target = if forward {
+            hunks.into_iter().find(|&line| line > current)
+        } else {
+            hunks.into_iter().rev().find(|&line| line < current)
+        };
+        if let Some(line) = target {
+            self.detail_scroll = (line as u16).min(self.detail_max_scroll.get());
+        }
+    }
+
+    fn open_commit(&mut self) {
+        let Some(wt) = self.selected_worktree() else {
+            return;
+        };
+        let Some(commit) = self.commits.get(self.log_cursor) else {
+            return;
+        };
+        match crate::git::commit_diff(&wt.path, &commit.hash) {
+            Ok(diff) => {
+                self.commit_diff = Some(diff);
+                self.detail_scroll = 0;
+            }
+            Err(e) => self.error = Some(e.to_string()),
+        }
+    }
+
+    fn toggle_selected_task(&mut self) {
+        let Some(content) = self.status_content.clone() else {
+            return;
+        };
+        let tasks = crate::status::find_tasks(&content);
+        if let Some(task) = tasks.get(self.task_cursor) {
+            let updated = crate::status::toggle_task(&content, task.line);
+            self.write_status_content(updated);
+        }
+    }
+
+    /// Write the selected worktree's status file and update its progress in place
+    fn write_status_content(&mut self, content: String) {
+        let Some(wt) = self.worktrees.get(self.selected) else {
+            return;
+        };
+        let status_path = self.status_path(wt);
+        if let Err(e) = std::fs::write(&status_path, &content) {
+            self.error = Some(format!("Failed to write status file: {}", e));
+            return;
+        }
+
+        let wt = &mut self.worktrees[self.selected];
+        wt.status = crate::status::parse_status_file(&content);
+        wt.status.has_file = true;
+        if self.revalidating.is_some() {
+            self.updated_while_revalidating.insert(wt.path.clone());
+        }
+        self.status_content = Some(content);
+    }
+
+    /// Source line of the task under the cursor while the notes pane is focused
+    pub fn task_cursor_line(&self) -> Option<usize> {
+        if !matches!(self.mode, AppMode::Tasks | AppMode::AddingTask) {
+            return None;
+        }
+        let content = self.status_content.as_deref()?;
+        crate::status::find_tasks(content)
+            .get(self.task_cursor)
+            .map(|t| t.line)
+    }
+
+    fn merge_main(&mut self) -> Result<()> {
+        if let Some(wt) = self.selected_worktree() {
+            if wt.is_main {
+                self.error = Some("Cannot merge main into itself".to_string());
+                return Ok(());
+            }
+            if let Some(op) = wt.operation {
+                self.error = Some(format!(
+                    "A {} is in progress here; continue or abort it before merging main",
+                    op.command()
+                ));
+                return Ok(());
+            }
+
+            match crate::git::merge_main_ff(&wt.path) {
+                Ok(()) => {
+                    self.refresh_worktrees();
+                    self.load_status_content();
+                }
+                Err(e) => {
+                    self.error = Some(e.to_string());
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Copy a markdown report of all worktrees, e.g. for a standup
+    fn copy_report(&mut self) {
+        let report = crate::report::render(
+            &self.worktrees,
+            crate::report::ReportFormat::Markdown,
+            &self.config.report,
+        );
+        match crate::report::copy_to_clipboard(&report) {
+            Ok(()) => self.notice = Some("Report copied to clipboard".to_string()),
+            Err(e) => self.error = Some(e.to_string()),
+        }
+    }
+
+    fn continue_operation(&mut self) {
+        let Some(wt) = self.selected_worktree() else {
+            return;
+        };
+        let Some(op) = wt.operation else {
+            self.error = Some("Nothing to continue: no rebase, merge or cherry-pick in progress".to_string());
+            return;
+        };
+        match crate::git::continue_operation(&wt.path, op) {
+            Ok(()) => self.notice = Some(format!("Continued {}", op.command())),
+            Err(e) => self.error = Some(e.to_string()),
+        }
+        self.refresh_worktrees();
+    }
+
+    fn start_abort(&mut self) {
+        match self.selected_worktree() {
+            Some(wt) if wt.operation.is_some() => self.mode = AppMode::ConfirmAbort,
+            Some(_) => {
+                self.error = Some("Nothing to abort: no rebase, merge or cherry-pick in progress".to_string())
+            }
+            None => {}
+        }
+    }
+
+    fn handle_abort_key(&mut self, key: KeyCode) -> Result<()> {
+        self.mode = AppMode::Normal;
+        if !matches!(key, KeyCode::Char('y') | KeyCode::Char('Y')) {
+            return Ok(());
+        }
+        let Some(wt) = self.selected_worktree() else {
+            return Ok(());
+        };
+        let Some(op) = wt.operation else {
+            return Ok(());
+        };
+        match crate::git::abort_operation(&wt.path, op) {
+            Ok(()) => self.notice = Some(format!("Aborted {}", op.command())),
+            Err(e) => self.error = Some(e.to_string()),
+        }
+        self.refresh_worktrees();
+        self.load_status_content();
+        Ok(())
+    }
+
+    fn push(&mut self) {
+        let Some(wt) = self.selected_worktree() else {
+            return;
+        };
+        let Some(branch) = wt.branch.as_deref() else {
+            self.error = Some("Cannot push a detached HEAD".to_string());
+            return;
+        };
+
+        // A gone upstream is treated like a first push so the branch is recreated
+        let has_upstream = wt.upstream.as_ref().is_some_and(|u| !u.gone);
+        match crate::git::push_branch(&wt.path, branch, has_upstream) {
+            Ok(()) => {
+                self.refresh_worktrees();
+            }
+            Err(e) => {
+                self.error = Some(e.to_string());
+            }
+        }
+    }
+
+    /// Select the worktree wtm was started in, the innermost one containing
+    /// the start directory
+    fn select_start_worktree(&mut self) {
+        let start = std::fs::canonicalize(&self.start_dir).unwrap_or_else(|_| self.start_dir.clone());
+        let found = self
+            .worktrees
+            .iter()
+            .enumerate()
+            .filter(|(_, wt)| {
+                let path = std::fs::canonicalize(&wt.path).unwrap_or_else(|_| wt.path.clone());
+                start.starts_with(path)
+            })
+            .max_by_key(|(_, wt)| wt.path.components().count())
+            .map(|(i, _)| i)
+            // e.g. the top of a bare layout, which no worktree contains
+            .or_else(|| self.worktrees.iter().position(|wt| wt.is_main));
+        if let Some(index) = found {
+            self.selected = index;
+            self.apply_filter();
+        }
+    }
+
+    /// Exit into the n-th (0-based) worktree as currently listed
+    fn jump_to_number(&mut self, n: usize) {
+        let row = self
+            .rows
+            .iter()
+            .enumerate()
+            .filter(|(_, r)| matches!(r, ListRow::Worktree(i) if !self.worktrees[*i].is_bare))
+            .nth(n)
+            .map(|(row, _)| row);
+        if let Some(row) = row {
+            self.move_cursor(row);
+            self.exit_to_worktree();
+        }
+    }
+
+    fn exit_to_worktree(&mut self) {
+        if self.selected_worktree().is_some_and(|wt| wt.is_bare) {
+            self.error = Some("The bare repository has no checkout, pick a worktree".to_string());
+            return;
+        }
+        if let Some(path) = self.selected_worktree().map(|wt| wt.path.clone()) {
+            self.state.record_enter(&path);
+            self.save_state();
+            self.exit_path = Some(path);
+            self.should_quit = true;
+        }
+    }
+
+    pub fn selected_worktree(&self) -> Option<&Worktree> {
+        match self.rows.get(self.cursor()) {
+            Some(ListRow::Worktree(index)) => self.worktrees.get(*index),
+            _ => None,
+        }
+    }
+
+    /// Where a worktree's status file lives for the configured storage
+    pub fn status_path(&self, wt: &Worktree) -> PathBuf {
+        crate::status::status_path(self.config.notes_storage, &wt.path, wt.branch.as_deref())
+    }
+
+    /// Path of a worktree's status file, generating it from the template if missing
+    fn ensure_status_file(&self, wt: &Worktree) -> std::io::Result<PathBuf> {
+        let status_path = self.status_path(wt);
+        if !status_path.exists() {
+            if let Some(parent) = status_path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            let branch = wt.branch.as_deref().unwrap_or("unknown");
+            let content = self.status_file_for(branch, &Self::main_branch(&wt.repo), None);
+            std::fs::write(&status_path, content)?;
+        }
+        Ok(status_path)
+    }
+
+    /// Status file content for a branch, from the named template or the one
+    /// its branch name selects
+    fn status_file_for(&self, branch: &str, base_branch: &str, template: Option<&str>) -> String {
+        let name = match template {
+            Some(name) => name.to_string(),
+            None => {
+                let templates = crate::status::list_templates(&self.checkout(), &self.config);
+                crate::status::select_template(branch, &templates, &self.config)
+            }
+        };
+        let checkout = self.checkout();
+        let template = crate::status::load_template(&name, &checkout, &self.config);
+        let author = crate::git::user_name(&checkout).unwrap_or_default();
+
+        crate::status::generate_status_file(
+            &template,
+            &crate::status::TemplateVars {
+                branch,
+                base_branch,
+                author: &author,
+            },
+        )
+    }
+
+    /// main or master, whichever the repository has; not necessarily what
+    /// the home worktree has checked out
+    fn main_branch(repo_path: &Path) -> String {
+        crate::git::find_main_branch(repo_path).unwrap_or_else(|| "main".to_string())
+    }
+
+    /// Template for the worktree being created: picked with ↑/↓, else by branch name
+    pub fn create_template(&self) -> (String, bool) {
+        match self.template_choice.and_then(|i| self.templates.get(i)) {
+            Some(name) => (name.clone(), false),
+            None => {
+                let name = crate::status::select_template(self.input.trim(), &self.templates, &self.config);
+                (name, true)
+            }
+        }
     }
 }
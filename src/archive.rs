@@ -0,0 +1,124 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::git::Worktree;
+
+/// Notes and final state of a deleted worktree
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveEntry {
+    pub branch: String,
+    pub path: PathBuf,
+    pub commit: String,
+    pub archived_at: i64,               // unix seconds
+    pub last_commit_time: Option<i64>,  // unix seconds
+    pub progress: (u32, u32),           // (checked, total)
+    pub purpose: Option<String>,
+    pub notes: Option<String>,          // full status file
+}
+
+impl ArchiveEntry {
+    pub fn new(wt: &Worktree, notes: Option<String>) -> Self {
+        Self {
+            branch: wt.branch.clone().unwrap_or_else(|| "(detached)".to_string()),
+            path: wt.path.clone(),
+            commit: wt.commit.clone(),
+            archived_at: crate::age::now(),
+            last_commit_time: wt.last_commit_time,
+            progress: wt.status.progress,
+            purpose: wt.status.purpose.clone(),
+            notes,
+        }
+    }
+
+    /// Case-insensitive match on branch, purpose and notes; every word must match
+    pub fn matches(&self, query: &str) -> bool {
+        let haystack = format!(
+            "{}\n{}\n{}",
+            self.branch,
+            self.purpose.as_deref().unwrap_or(""),
+            self.notes.as_deref().unwrap_or("")
+        )
+        .to_lowercase();
+        query
+            .split_whitespace()
+            .all(|word| haystack.contains(&word.to_lowercase()))
+    }
+}
+
+/// Per-repo journal of deleted worktrees, newest last
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Archive {
+    #[serde(default)]
+    pub entries: Vec<ArchiveEntry>,
+}
+
+impl Archive {
+    /// Load a repo's archive, empty if there is none yet
+    pub fn load(repo_path: &Path) -> Result<Self> {
+        let path = archive_path(repo_path)?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        Ok(toml::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    pub fn append(repo_path: &Path, entry: ArchiveEntry) -> Result<()> {
+        let mut archive = Self::load(repo_path)?;
+        archive.entries.push(entry);
+
+        let path = archive_path(repo_path)?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, toml::to_string_pretty(&archive)?)?;
+        Ok(())
+    }
+
+    /// Entries matching a query, newest first
+    pub fn search(&self, query: &str) -> Vec<&ArchiveEntry> {
+        self.entries.iter().rev().filter(|e| e.matches(query)).collect()
+    }
+}
+
+/// `<data dir>/archive/<repo>.toml`, shared by all worktrees of a repo
+fn archive_path(repo_path: &Path) -> Result<PathBuf> {
+    let (_, common_dir) =
+        crate::git::git_dirs(repo_path).ok_or_else(|| anyhow!("Not a git repository"))?;
+    Ok(crate::state::data_dir()
+        .join("archive")
+        .join(format!("{}.toml", crate::status::repo_key(&common_dir))))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_search_newest_first() {
+        let entry = |branch: &str, notes: &str| ArchiveEntry {
+            branch: branch.to_string(),
+            path: PathBuf::from("/tmp/x"),
+            commit: "abc1234".to_string(),
+            archived_at: 0,
+            last_commit_time: None,
+            progress: (0, 0),
+            purpose: None,
+            notes: Some(notes.to_string()),
+        };
+        let archive = Archive {
+            entries: vec![
+                entry("feature/login", "OAuth flow"),
+                entry("fix/crash", "null check in oauth callback"),
+                entry("feature/api", "REST"),
+            ],
+        };
+
+        let branches: Vec<&str> = archive.search("OAUTH").iter().map(|e| e.branch.as_str()).collect();
+        assert_eq!(branches, vec!["fix/crash", "feature/login"]);
+        assert_eq!(archive.search("feature oauth").len(), 1);
+        assert_eq!(archive.search("").len(), 3);
+    }
+}
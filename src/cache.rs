@@ -0,0 +1,152 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::config::NotesStorage;
+use crate::git::Worktree;
+
+/// Worktree metadata from the last run, so the list can be shown before git
+/// has been asked about every worktree
+#[derive(Default, Serialize, Deserialize)]
+pub struct Cache {
+    #[serde(default)]
+    entries: Vec<Entry>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Entry {
+    key: Key,
+    worktree: Worktree,
+}
+
+/// What a cached worktree was loaded from; any difference means git has to
+/// be asked again. Uncommitted changes aren't covered, they are always re-read.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Key {
+    branch: Option<String>,
+    head: String,
+    main_head: Option<String>, // ahead/behind are relative to main
+    status_mtime: Option<u128>, // nanoseconds
+}
+
+impl Key {
+    fn new(path: &Path, branch: Option<&str>, head: &str, main_head: Option<&str>, notes: NotesStorage) -> Self {
+        let status_file = crate::status::status_path(notes, path, branch);
+        Self {
+            branch: branch.map(str::to_string),
+            head: head.to_string(),
+            main_head: main_head.map(str::to_string),
+            status_mtime: mtime(&status_file),
+        }
+    }
+}
+
+fn mtime(path: &Path) -> Option<u128> {
+    let modified = fs::metadata(path).ok()?.modified().ok()?;
+    Some(modified.duration_since(UNIX_EPOCH).ok()?.as_nanos())
+}
+
+impl Cache {
+    /// Load the repo's cache, empty if there is none or it can't be read
+    pub fn load(repo_path: &Path) -> Self {
+        cache_path(repo_path)
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The worktrees as they were last seen, possibly out of date
+    pub fn worktrees(&self) -> Vec<Worktree> {
+        self.entries.iter().map(|e| e.worktree.clone()).collect()
+    }
+
+    /// List worktrees, only querying git about the ones that changed since
+    /// they were cached
+    pub fn revalidate(mut self, repo_path: &Path, notes: NotesStorage) -> Result<Vec<Worktree>> {
+        let main_head = crate::git::main_commit(repo_path);
+        crate::git::list_worktrees_reusing(repo_path, notes, |path, branch, head| {
+            let index = self.entries.iter().position(|e| e.worktree.path == path)?;
+            let key = Key::new(path, branch, head, main_head.as_deref(), notes);
+            (self.entries[index].key == key).then(|| self.entries.swap_remove(index).worktree)
+        })
+    }
+
+    /// Save the given worktrees as the repo's cache
+    pub fn save(repo_path: &Path, worktrees: &[Worktree], notes: NotesStorage) -> Result<()> {
+        let Some(path) = cache_path(repo_path) else {
+            return Ok(());
+        };
+        let main_head = crate::git::main_commit(repo_path);
+        let cache = Cache {
+            entries: worktrees
+                .iter()
+                .map(|wt| Entry {
+                    key: Key::new(&wt.path, wt.branch.as_deref(), &wt.commit, main_head.as_deref(), notes),
+                    worktree: wt.clone(),
+                })
+                .collect(),
+        };
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, serde_json::to_string(&cache)?)?;
+        Ok(())
+    }
+}
+
+/// `$XDG_CACHE_HOME/wtm/<repo>.json`, or under `~/.cache`
+fn cache_path(repo_path: &Path) -> Option<PathBuf> {
+    let (_, common_dir) = crate::git::git_dirs(repo_path)?;
+    let cache_dir = std::env::var("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| {
+            dirs::home_dir()
+                .unwrap_or_else(|| PathBuf::from("."))
+                .join(".cache")
+        });
+    Some(
+        cache_dir
+            .join("wtm")
+            .join(format!("{}.json", crate::status::repo_key(&common_dir))),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entries_round_trip() {
+        let mut status = crate::status::parse_status_file("---\npriority: 1\ntags: [a, b]\n---\n## Purpose\nLogin\n- [ ] Tests\n");
+        status.has_file = true;
+        let worktree = Worktree {
+            path: PathBuf::from("/src/app-login"),
+            branch: Some("feature/login".to_string()),
+            commit: "abc1234".to_string(),
+            operation: Some(crate::git::Operation::Rebase),
+            status,
+            ahead: 3,
+            behind: 1,
+            last_commit_time: Some(1_700_000_000),
+            ..Default::default()
+        };
+        let key = Key::new(&worktree.path, worktree.branch.as_deref(), "abc1234", None, NotesStorage::Worktree);
+        let cache = Cache { entries: vec![Entry { key, worktree }] };
+
+        let loaded: Cache = serde_json::from_str(&serde_json::to_string(&cache).unwrap()).unwrap();
+        let wt = &loaded.worktrees()[0];
+        assert_eq!(wt.status.purpose.as_deref(), Some("Login"));
+        assert_eq!(wt.status.meta.priority.as_deref(), Some("1"));
+        assert_eq!(wt.status.meta.tags, ["a", "b"]);
+        assert_eq!(wt.operation, Some(crate::git::Operation::Rebase));
+        assert_eq!(loaded.entries[0].key, cache.entries[0].key);
+    }
+}
@@ -1,21 +1,28 @@
 use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+use crate::report::ReportTemplate;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub enum CommandMode {
+    #[default]
     Replace, // Take over terminal (like lazygit)
     Detach,  // Spawn in background (like IDE)
 }
 
-impl Default for CommandMode {
-    fn default() -> Self {
-        Self::Replace
-    }
+/// Where status files are kept
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum NotesStorage {
+    #[default]
+    Worktree, // .worktree-status.md in the worktree root
+    GitDir,   // .git/worktrees/<name>/wtm/status.md, removed with the worktree
+    DataDir,  // ~/.local/share/wtm/notes/<repo>/<branch>.md, kept per branch
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -31,20 +38,61 @@ pub enum Shortcut {
     },
 }
 
+/// Picks a status template for branches matching `pattern` (`*` wildcards)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateRule {
+    pub pattern: String,
+    pub template: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
-    #[serde(default = "default_shortcuts")]
+    /// Worktrees with no activity for this many days are highlighted as stale
+    #[serde(default = "default_stale_days")]
+    pub stale_days: u32,
+    #[serde(default)]
+    pub notes_storage: NotesStorage,
+    /// Add `.worktree-status.md` to `.git/info/exclude` so notes don't make worktrees dirty
+    #[serde(default)]
+    pub exclude_status_file: bool,
+    #[serde(default = "default_shortcuts", deserialize_with = "merge_shortcuts")]
     pub shortcuts: HashMap<String, Shortcut>,
+    /// Inline status file templates by name
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub templates: HashMap<String, String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub template_rules: Vec<TemplateRule>,
+    /// Report layouts by format name (markdown, html, text)
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub report: HashMap<String, ReportTemplate>,
+    /// Repositories shown in the dashboard, `~` is expanded
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub repos: Vec<String>,
+    /// Directory whose repositories are all shown in the dashboard
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repos_root: Option<String>,
 }
 
 impl Default for Config {
     fn default() -> Self {
         Self {
+            stale_days: default_stale_days(),
+            notes_storage: NotesStorage::default(),
+            exclude_status_file: false,
             shortcuts: default_shortcuts(),
+            templates: HashMap::new(),
+            template_rules: Vec::new(),
+            report: HashMap::new(),
+            repos: Vec::new(),
+            repos_root: None,
         }
     }
 }
 
+fn default_stale_days() -> u32 {
+    30
+}
+
 fn default_shortcuts() -> HashMap<String, Shortcut> {
     let mut shortcuts = HashMap::new();
 
@@ -52,8 +100,23 @@ fn default_shortcuts() -> HashMap<String, Shortcut> {
     shortcuts.insert("n".to_string(), Shortcut::BuiltIn { action: "create".to_string() });
     shortcuts.insert("d".to_string(), Shortcut::BuiltIn { action: "delete".to_string() });
     shortcuts.insert("e".to_string(), Shortcut::BuiltIn { action: "edit".to_string() });
+    shortcuts.insert("x".to_string(), Shortcut::BuiltIn { action: "tasks".to_string() });
+    shortcuts.insert("D".to_string(), Shortcut::BuiltIn { action: "diff".to_string() });
+    shortcuts.insert("S".to_string(), Shortcut::BuiltIn { action: "stashes".to_string() });
+    shortcuts.insert("L".to_string(), Shortcut::BuiltIn { action: "log".to_string() });
+    shortcuts.insert("i".to_string(), Shortcut::BuiltIn { action: "edit_purpose".to_string() });
+    shortcuts.insert("o".to_string(), Shortcut::BuiltIn { action: "edit_notes".to_string() });
     shortcuts.insert("m".to_string(), Shortcut::BuiltIn { action: "merge_main".to_string() });
+    shortcuts.insert("M".to_string(), Shortcut::BuiltIn { action: "set_home".to_string() });
+    shortcuts.insert("C".to_string(), Shortcut::BuiltIn { action: "continue_operation".to_string() });
+    shortcuts.insert("A".to_string(), Shortcut::BuiltIn { action: "abort_operation".to_string() });
+    shortcuts.insert("p".to_string(), Shortcut::BuiltIn { action: "push".to_string() });
     shortcuts.insert("t".to_string(), Shortcut::BuiltIn { action: "toggle_view".to_string() });
+    shortcuts.insert("s".to_string(), Shortcut::BuiltIn { action: "sort".to_string() });
+    shortcuts.insert("z".to_string(), Shortcut::BuiltIn { action: "group".to_string() });
+    shortcuts.insert("R".to_string(), Shortcut::BuiltIn { action: "report".to_string() });
+    shortcuts.insert("H".to_string(), Shortcut::BuiltIn { action: "history".to_string() });
+    shortcuts.insert("a".to_string(), Shortcut::BuiltIn { action: "dashboard".to_string() });
     shortcuts.insert("r".to_string(), Shortcut::BuiltIn { action: "refresh".to_string() });
     shortcuts.insert("?".to_string(), Shortcut::BuiltIn { action: "help".to_string() });
     shortcuts.insert("q".to_string(), Shortcut::BuiltIn { action: "quit".to_string() });
@@ -72,6 +135,17 @@ fn default_shortcuts() -> HashMap<String, Shortcut> {
     shortcuts
 }
 
+/// The user's `[shortcuts]` on top of the defaults, so keys bound by newer
+/// versions show up in configs written by older ones
+fn merge_shortcuts<'de, D>(deserializer: D) -> Result<HashMap<String, Shortcut>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let mut shortcuts = default_shortcuts();
+    shortcuts.extend(HashMap::<String, Shortcut>::deserialize(deserializer)?);
+    Ok(shortcuts)
+}
+
 impl Config {
     pub fn load() -> Result<Self> {
         let config_path = Self::config_path()?;
@@ -115,8 +189,44 @@ r#"# wtm configuration file
 #   $2 or $branch - branch name
 #   $repo         - main repo path
 #
+# stale_days: worktrees without commits, edits or visits for this many
+#   days are highlighted in the list
+#
+# notes_storage: where status files live
+#   worktree - .worktree-status.md in the worktree root (shows up in git status)
+#   git_dir  - .git/worktrees/<name>/wtm/status.md
+#   data_dir - ~/.local/share/wtm/notes/<repo>/<branch>.md
+#   After switching, run `wtm migrate-notes` to move existing .worktree-status.md files.
+# exclude_status_file: with worktree storage, add .worktree-status.md to
+#   .git/info/exclude so it doesn't count as an uncommitted change
+#
+# Status file templates come from <repo>/.wtm/templates/<name>.md or
+# [templates] below. A branch gets the first matching template rule, else the
+# template named after its prefix (fix/crash -> fix), else "default":
+#   [templates]
+#   bugfix = "Fix for {{ticket}} on {{branch_name}}\n\n## Status\n- [ ] Reproduced\n"
+#   [[template_rules]]
+#   pattern = "release/*"
+#   template = "release"
+# Placeholders: {{branch_name}} {{base_branch}} {{author}} {{date}} {{ticket}}
+#
+# Reports (`wtm report`, or the report action which copies markdown to the
+# clipboard) can be laid out per format with [report.markdown], [report.html]
+# and [report.text], each with optional header, item and footer:
+#   [report.markdown]
+#   item = "- {{branch}} {{progress}}: {{purpose}}\n"
+# Placeholders: {{branch}} {{path}} {{purpose}} {{state}} {{progress}} {{dirty}}
+#   {{ahead_behind}} {{ahead}} {{behind}} {{open_tasks}}, and {{date}} in header/footer
+#
+# The dashboard (`wtm dashboard`, or the dashboard action) lists the worktrees
+# of several repositories, grouped by repo:
+#   repos = ["~/src/api", "~/src/web"]
+#   repos_root = "~/src"   # every repository directly inside
+#
 # Built-in actions:
-#   create, delete, edit, merge_main, toggle_view, refresh, help, quit, cd
+#   create, delete, edit, tasks, log, diff, stashes, edit_purpose, edit_notes, merge_main,
+#   set_home, continue_operation, abort_operation, push, toggle_view, sort, group, dashboard,
+#   report, history, refresh, help, quit, cd
 
 {}"#, content);
 
@@ -139,4 +249,83 @@ r#"# wtm configuration file
     pub fn get_shortcut(&self, key: &str) -> Option<&Shortcut> {
         self.shortcuts.get(key)
     }
+
+    /// Repositories for the dashboard: `repos`, then those found in `repos_root`
+    pub fn dashboard_repos(&self) -> Vec<PathBuf> {
+        let mut repos: Vec<PathBuf> = self.repos.iter().map(|r| expand_home(r)).collect();
+
+        if let Some(root) = &self.repos_root {
+            let mut found: Vec<PathBuf> = fs::read_dir(expand_home(root))
+                .into_iter()
+                .flatten()
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|path| is_repo(path))
+                .collect();
+            found.sort();
+            repos.extend(found);
+        }
+
+        let mut seen = Vec::new();
+        repos.retain(|r| {
+            let key = fs::canonicalize(r).unwrap_or_else(|_| r.clone());
+            let new = !seen.contains(&key);
+            seen.push(key);
+            new
+        });
+        repos
+    }
+}
+
+/// Replace a leading `~` with the home directory
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix('~'), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest.trim_start_matches('/')),
+        _ => PathBuf::from(path),
+    }
+}
+
+/// A main worktree (with `.git` directory), a bare repository, or a bare
+/// layout whose `.git` file points at it. Linked worktrees also have a `.git`
+/// file, into `.../worktrees/<name>`, and are listed with their repository.
+fn is_repo(path: &Path) -> bool {
+    let dot_git = path.join(".git");
+    if dot_git.is_dir() || (path.join("HEAD").is_file() && path.join("objects").is_dir()) {
+        return true;
+    }
+    fs::read_to_string(dot_git)
+        .ok()
+        .and_then(|content| content.trim().strip_prefix("gitdir: ").map(str::to_string))
+        .is_some_and(|dir| !Path::new(&dir).parent().is_some_and(|p| p.ends_with("worktrees")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_old_config_gets_new_shortcuts() {
+        // As written by the first release, before push, tasks etc. existed
+        let content = r#"
+[shortcuts.n]
+action = "create"
+
+[shortcuts.q]
+action = "quit"
+
+[shortcuts.g]
+cmd = "tig"
+mode = "replace"
+"#;
+        let config: Config = toml::from_str(content).unwrap();
+        let action = |key: &str| match config.get_shortcut(key) {
+            Some(Shortcut::BuiltIn { action }) => action.as_str(),
+            Some(Shortcut::Command { cmd, .. }) => cmd.as_str(),
+            None => "",
+        };
+        assert_eq!(action("p"), "push");
+        assert_eq!(action("x"), "tasks");
+        assert_eq!(action("M"), "set_home");
+        assert_eq!(action("g"), "tig");
+    }
 }
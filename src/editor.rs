@@ -0,0 +1,313 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+const MAX_UNDO: usize = 100;
+
+/// Minimal multi-line text editor used for inline edits in the detail pane.
+/// The cursor column counts chars, not bytes.
+#[derive(Debug, Default, Clone)]
+pub struct TextEditor {
+    lines: Vec<String>,
+    row: usize,
+    col: usize,
+    undo: Vec<(Vec<String>, usize, usize)>,
+    typing: bool, // the last edit was a typed char, so the next one joins its undo step
+}
+
+impl TextEditor {
+    pub fn new(text: &str) -> Self {
+        let mut lines: Vec<String> = text.lines().map(str::to_string).collect();
+        if lines.is_empty() {
+            lines.push(String::new());
+        }
+        let row = lines.len() - 1;
+        let col = lines[row].chars().count();
+        Self {
+            lines,
+            row,
+            col,
+            ..Default::default()
+        }
+    }
+
+    pub fn text(&self) -> String {
+        self.lines.join("\n")
+    }
+
+    pub fn lines(&self) -> &[String] {
+        &self.lines
+    }
+
+    /// (row, col) of the cursor
+    pub fn cursor(&self) -> (usize, usize) {
+        (self.row, self.col)
+    }
+
+    pub fn input(&mut self, key: KeyEvent) {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        let alt = key.modifiers.contains(KeyModifiers::ALT);
+
+        match key.code {
+            KeyCode::Char('z') if ctrl => self.undo(),
+            KeyCode::Char('w') if ctrl => self.delete_word(),
+            KeyCode::Backspace if ctrl || alt => self.delete_word(),
+            KeyCode::Char('a') if ctrl => self.col = 0,
+            KeyCode::Char('e') if ctrl => self.col = self.line_len(),
+            KeyCode::Char('b') if alt => self.word_left(),
+            KeyCode::Char('f') if alt => self.word_right(),
+            KeyCode::Left if ctrl || alt => self.word_left(),
+            KeyCode::Right if ctrl || alt => self.word_right(),
+            KeyCode::Char(_) if ctrl || alt => {}
+            KeyCode::Char(c) => self.insert_char(c),
+            KeyCode::Enter => self.newline(),
+            KeyCode::Backspace => self.backspace(),
+            KeyCode::Delete => self.delete(),
+            KeyCode::Left => self.left(),
+            KeyCode::Right => self.right(),
+            KeyCode::Up if self.row > 0 => {
+                self.row -= 1;
+                self.col = self.col.min(self.line_len());
+            }
+            KeyCode::Down if self.row + 1 < self.lines.len() => {
+                self.row += 1;
+                self.col = self.col.min(self.line_len());
+            }
+            KeyCode::Home => self.col = 0,
+            KeyCode::End => self.col = self.line_len(),
+            _ => {}
+        }
+
+        if !matches!(key.code, KeyCode::Char(_)) || ctrl || alt {
+            self.typing = false;
+        }
+    }
+
+    fn line_len(&self) -> usize {
+        self.lines[self.row].chars().count()
+    }
+
+    /// Byte offset of a char column in the current line
+    fn byte_at(&self, col: usize) -> usize {
+        byte_index(&self.lines[self.row], col)
+    }
+
+    fn snapshot(&mut self) {
+        self.undo.push((self.lines.clone(), self.row, self.col));
+        if self.undo.len() > MAX_UNDO {
+            self.undo.remove(0);
+        }
+    }
+
+    fn undo(&mut self) {
+        if let Some((lines, row, col)) = self.undo.pop() {
+            self.lines = lines;
+            self.row = row;
+            self.col = col;
+        }
+    }
+
+    fn insert_char(&mut self, c: char) {
+        // A run of typed chars is one undo step, split at word boundaries
+        if !self.typing || c == ' ' {
+            self.snapshot();
+        }
+        self.typing = true;
+
+        let at = self.byte_at(self.col);
+        self.lines[self.row].insert(at, c);
+        self.col += 1;
+    }
+
+    fn newline(&mut self) {
+        self.snapshot();
+        let at = self.byte_at(self.col);
+        let rest = self.lines[self.row].split_off(at);
+        self.lines.insert(self.row + 1, rest);
+        self.row += 1;
+        self.col = 0;
+    }
+
+    fn backspace(&mut self) {
+        if self.col > 0 {
+            self.snapshot();
+            self.col -= 1;
+            let at = self.byte_at(self.col);
+            self.lines[self.row].remove(at);
+        } else if self.row > 0 {
+            self.snapshot();
+            let line = self.lines.remove(self.row);
+            self.row -= 1;
+            self.col = self.line_len();
+            self.lines[self.row].push_str(&line);
+        }
+    }
+
+    fn delete(&mut self) {
+        if self.col < self.line_len() {
+            self.snapshot();
+            let at = self.byte_at(self.col);
+            self.lines[self.row].remove(at);
+        } else if self.row + 1 < self.lines.len() {
+            self.snapshot();
+            let next = self.lines.remove(self.row + 1);
+            self.lines[self.row].push_str(&next);
+        }
+    }
+
+    /// Delete back to the start of the previous word (like Ctrl+W in a shell)
+    fn delete_word(&mut self) {
+        if self.col == 0 {
+            self.backspace();
+            return;
+        }
+        self.snapshot();
+        let end = self.byte_at(self.col);
+        self.word_left();
+        let start = self.byte_at(self.col);
+        self.lines[self.row].replace_range(start..end, "");
+    }
+
+    fn left(&mut self) {
+        if self.col > 0 {
+            self.col -= 1;
+        } else if self.row > 0 {
+            self.row -= 1;
+            self.col = self.line_len();
+        }
+    }
+
+    fn right(&mut self) {
+        if self.col < self.line_len() {
+            self.col += 1;
+        } else if self.row + 1 < self.lines.len() {
+            self.row += 1;
+            self.col = 0;
+        }
+    }
+
+    fn word_left(&mut self) {
+        if self.col == 0 {
+            self.left();
+            return;
+        }
+        let chars: Vec<char> = self.lines[self.row].chars().collect();
+        let mut col = self.col;
+        while col > 0 && chars[col - 1].is_whitespace() {
+            col -= 1;
+        }
+        while col > 0 && !chars[col - 1].is_whitespace() {
+            col -= 1;
+        }
+        self.col = col;
+    }
+
+    fn word_right(&mut self) {
+        if self.col == self.line_len() {
+            self.right();
+            return;
+        }
+        let chars: Vec<char> = self.lines[self.row].chars().collect();
+        let mut col = self.col;
+        while col < chars.len() && !chars[col].is_whitespace() {
+            col += 1;
+        }
+        while col < chars.len() && chars[col].is_whitespace() {
+            col += 1;
+        }
+        self.col = col;
+    }
+}
+
+/// Byte offset of a char column in `text`
+fn byte_index(text: &str, col: usize) -> usize {
+    text.char_indices().nth(col).map_or(text.len(), |(i, _)| i)
+}
+
+/// Apply a key to a single-line prompt (filter, commit message, new task)
+/// whose cursor counts chars. Returns whether the text changed.
+pub fn edit_line(text: &mut String, cursor: &mut usize, key: KeyCode) -> bool {
+    match key {
+        KeyCode::Backspace if *cursor > 0 => {
+            *cursor -= 1;
+            text.remove(byte_index(text, *cursor));
+            true
+        }
+        KeyCode::Left if *cursor > 0 => {
+            *cursor -= 1;
+            false
+        }
+        KeyCode::Right if *cursor < text.chars().count() => {
+            *cursor += 1;
+            false
+        }
+        KeyCode::Char(c) => {
+            text.insert(byte_index(text, *cursor), c);
+            *cursor += 1;
+            true
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(editor: &mut TextEditor, code: KeyCode, modifiers: KeyModifiers) {
+        editor.input(KeyEvent::new(code, modifiers));
+    }
+
+    fn type_str(editor: &mut TextEditor, s: &str) {
+        for c in s.chars() {
+            press(editor, KeyCode::Char(c), KeyModifiers::NONE);
+        }
+    }
+
+    #[test]
+    fn test_edit_and_undo() {
+        let mut editor = TextEditor::new("Fix login");
+        type_str(&mut editor, " flow");
+        press(&mut editor, KeyCode::Enter, KeyModifiers::NONE);
+        type_str(&mut editor, "ünïcode");
+        assert_eq!(editor.text(), "Fix login flow\nünïcode");
+        assert_eq!(editor.cursor(), (1, 7));
+
+        press(&mut editor, KeyCode::Char('w'), KeyModifiers::CONTROL);
+        assert_eq!(editor.text(), "Fix login flow\n");
+        press(&mut editor, KeyCode::Backspace, KeyModifiers::NONE);
+        assert_eq!(editor.text(), "Fix login flow");
+
+        press(&mut editor, KeyCode::Char('z'), KeyModifiers::CONTROL);
+        press(&mut editor, KeyCode::Char('z'), KeyModifiers::CONTROL);
+        assert_eq!(editor.text(), "Fix login flow\nünïcode");
+        press(&mut editor, KeyCode::Char('z'), KeyModifiers::CONTROL);
+        press(&mut editor, KeyCode::Char('z'), KeyModifiers::CONTROL);
+        press(&mut editor, KeyCode::Char('z'), KeyModifiers::CONTROL);
+        assert_eq!(editor.text(), "Fix login");
+    }
+
+    #[test]
+    fn test_edit_line_non_ascii() {
+        let (mut text, mut cursor) = (String::new(), 0);
+        for c in "café ✓".chars() {
+            edit_line(&mut text, &mut cursor, KeyCode::Char(c));
+        }
+        edit_line(&mut text, &mut cursor, KeyCode::Left);
+        edit_line(&mut text, &mut cursor, KeyCode::Left);
+        edit_line(&mut text, &mut cursor, KeyCode::Backspace);
+        edit_line(&mut text, &mut cursor, KeyCode::Char('e'));
+        assert_eq!(text, "cafe ✓");
+        assert_eq!(cursor, 4);
+        assert!(!edit_line(&mut text, &mut cursor, KeyCode::Right));
+    }
+
+    #[test]
+    fn test_word_movement() {
+        let mut editor = TextEditor::new("one two  three");
+        press(&mut editor, KeyCode::Left, KeyModifiers::CONTROL);
+        assert_eq!(editor.cursor(), (0, 9));
+        press(&mut editor, KeyCode::Left, KeyModifiers::CONTROL);
+        assert_eq!(editor.cursor(), (0, 4));
+        press(&mut editor, KeyCode::Right, KeyModifiers::CONTROL);
+        assert_eq!(editor.cursor(), (0, 9));
+    }
+}
@@ -0,0 +1,197 @@
+use crate::git::Worktree;
+
+/// Comparison used by numeric predicates like `progress:<50%`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Comparison {
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+    Equal,
+}
+
+impl Comparison {
+    fn compare(self, value: u32, target: u32) -> bool {
+        match self {
+            Self::Less => value < target,
+            Self::LessOrEqual => value <= target,
+            Self::Greater => value > target,
+            Self::GreaterOrEqual => value >= target,
+            Self::Equal => value == target,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Predicate {
+    Dirty,
+    Merged,
+    Ahead,
+    HasNotes,
+    HasStash,
+    Overdue,
+    Progress(Comparison, u32), // percentage of checked tasks
+    // Frontmatter fields, compared case-insensitively
+    State(String),
+    Owner(String),
+    Priority(String),
+    Ticket(String),
+    Tag(String),
+}
+
+/// A parsed list query: free-text terms plus `is:`/`has:`/`progress:` predicates
+/// and frontmatter fields like `state:review` or `tag:backend`.
+/// Every term and predicate must match for a worktree to be shown.
+#[derive(Debug, Default, Clone)]
+pub struct Filter {
+    terms: Vec<String>,
+    predicates: Vec<Predicate>,
+}
+
+impl Filter {
+    pub fn parse(query: &str) -> Self {
+        let mut filter = Filter::default();
+
+        for word in query.split_whitespace() {
+            match parse_predicate(word) {
+                Some(predicate) => filter.predicates.push(predicate),
+                None => filter.terms.push(word.to_lowercase()),
+            }
+        }
+
+        filter
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty() && self.predicates.is_empty()
+    }
+
+    pub fn matches(&self, wt: &Worktree) -> bool {
+        self.predicates.iter().all(|p| predicate_matches(p, wt))
+            && self.terms.iter().all(|t| term_matches(t, wt))
+    }
+}
+
+fn parse_predicate(word: &str) -> Option<Predicate> {
+    match word.to_lowercase().as_str() {
+        "is:dirty" => return Some(Predicate::Dirty),
+        "is:merged" => return Some(Predicate::Merged),
+        "is:ahead" => return Some(Predicate::Ahead),
+        "has:notes" => return Some(Predicate::HasNotes),
+        "has:stash" => return Some(Predicate::HasStash),
+        "is:overdue" => return Some(Predicate::Overdue),
+        _ => {}
+    }
+
+    if let Some((field, value)) = word.split_once(':') {
+        let value = value.to_lowercase();
+        let predicate = match field.to_lowercase().as_str() {
+            "state" => Some(Predicate::State(value)),
+            "owner" => Some(Predicate::Owner(value)),
+            "priority" => Some(Predicate::Priority(value)),
+            "ticket" => Some(Predicate::Ticket(value)),
+            "tag" => Some(Predicate::Tag(value)),
+            _ => None,
+        };
+        if predicate.is_some() {
+            return predicate;
+        }
+    }
+
+    let (field, value) = word.split_once(':')?;
+    if !field.eq_ignore_ascii_case("progress") {
+        return None;
+    }
+    let (comparison, rest) = if let Some(rest) = value.strip_prefix("<=") {
+        (Comparison::LessOrEqual, rest)
+    } else if let Some(rest) = value.strip_prefix(">=") {
+        (Comparison::GreaterOrEqual, rest)
+    } else if let Some(rest) = value.strip_prefix('<') {
+        (Comparison::Less, rest)
+    } else if let Some(rest) = value.strip_prefix('>') {
+        (Comparison::Greater, rest)
+    } else if let Some(rest) = value.strip_prefix('=') {
+        (Comparison::Equal, rest)
+    } else {
+        (Comparison::Equal, value)
+    };
+
+    let percent = rest.trim_end_matches('%').parse().ok()?;
+    Some(Predicate::Progress(comparison, percent))
+}
+
+fn predicate_matches(predicate: &Predicate, wt: &Worktree) -> bool {
+    match predicate {
+        Predicate::Dirty => wt.has_changes,
+        Predicate::Merged => wt.is_merged(),
+        Predicate::Ahead => wt.ahead > 0,
+        Predicate::HasNotes => wt.status.has_file,
+        Predicate::HasStash => wt.stashes > 0,
+        Predicate::Overdue => wt.status.meta.is_overdue(chrono::Local::now().date_naive()),
+        Predicate::Progress(comparison, target) => {
+            let (checked, total) = wt.status.progress;
+            // Worktrees without tasks have no progress to compare
+            total > 0 && comparison.compare(checked * 100 / total, *target)
+        }
+        Predicate::State(value) => field_is(&wt.status.meta.state, value),
+        Predicate::Owner(value) => field_is(&wt.status.meta.owner, value),
+        Predicate::Priority(value) => field_is(&wt.status.meta.priority, value),
+        Predicate::Ticket(value) => field_is(&wt.status.meta.ticket, value),
+        Predicate::Tag(value) => wt.status.meta.tags.iter().any(|t| t.to_lowercase() == *value),
+    }
+}
+
+fn field_is(field: &Option<String>, value: &str) -> bool {
+    field.as_deref().is_some_and(|f| f.to_lowercase() == value)
+}
+
+fn term_matches(term: &str, wt: &Worktree) -> bool {
+    let branch = wt.branch.as_deref().unwrap_or("(detached)");
+    let purpose = wt.status.purpose.as_deref().unwrap_or("");
+    let ticket = wt.status.meta.ticket.as_deref().unwrap_or("");
+
+    branch.to_lowercase().contains(term)
+        || wt.path.to_string_lossy().to_lowercase().contains(term)
+        || purpose.to_lowercase().contains(term)
+        || ticket.to_lowercase().contains(term)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_predicates_and_terms() {
+        let filter = Filter::parse("is:dirty Auth has:notes");
+        assert_eq!(filter.predicates, vec![Predicate::Dirty, Predicate::HasNotes]);
+        assert_eq!(filter.terms, vec!["auth".to_string()]);
+    }
+
+    #[test]
+    fn test_parse_progress() {
+        assert_eq!(
+            parse_predicate("progress:<50%"),
+            Some(Predicate::Progress(Comparison::Less, 50))
+        );
+        assert_eq!(
+            parse_predicate("progress:>=75"),
+            Some(Predicate::Progress(Comparison::GreaterOrEqual, 75))
+        );
+        assert_eq!(
+            parse_predicate("Progress:100%"),
+            Some(Predicate::Progress(Comparison::Equal, 100))
+        );
+        assert_eq!(parse_predicate("progress:lots"), None);
+    }
+
+    #[test]
+    fn test_parse_frontmatter_fields() {
+        assert_eq!(
+            parse_predicate("State:Review"),
+            Some(Predicate::State("review".to_string()))
+        );
+        assert_eq!(parse_predicate("tag:api"), Some(Predicate::Tag("api".to_string())));
+        assert_eq!(parse_predicate("is:overdue"), Some(Predicate::Overdue));
+        assert_eq!(parse_predicate("foo:bar"), None);
+    }
+}
@@ -0,0 +1,79 @@
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::{anyhow, Result};
+
+use super::worktree::find_main_branch;
+use super::ChangedFile;
+
+/// Staged and unstaged changes of one file. Untracked files are shown as
+/// entirely added.
+pub fn file_diff(worktree_path: &Path, file: &ChangedFile, word_diff: bool) -> Result<String> {
+    if file.is_untracked() {
+        // `--no-index` exits with 1 when the files differ, which they always do
+        let output = diff_command(worktree_path, word_diff)
+            .args(["--no-index", "--", "/dev/null", &file.path])
+            .output()?;
+        return Ok(String::from_utf8_lossy(&output.stdout).to_string());
+    }
+
+    let staged = run_diff(worktree_path, word_diff, &["--cached", "--", &file.path])?;
+    let unstaged = run_diff(worktree_path, word_diff, &["--", &file.path])?;
+
+    Ok(match (staged.is_empty(), unstaged.is_empty()) {
+        (false, false) => format!("Staged:\n{}\nUnstaged:\n{}", staged, unstaged),
+        (false, true) => staged,
+        _ => unstaged,
+    })
+}
+
+/// Everything the branch changed since it forked from the main branch, with a
+/// `--stat` summary on top
+pub fn branch_diff(worktree_path: &Path, branch: Option<&str>, word_diff: bool) -> Result<String> {
+    let main_branch = find_main_branch(worktree_path)
+        .ok_or_else(|| anyhow!("Could not find main or master branch"))?;
+    let branch = branch.ok_or_else(|| anyhow!("Cannot diff a detached HEAD against {}", main_branch))?;
+    if branch == main_branch {
+        return Err(anyhow!("This is the {} branch", main_branch));
+    }
+
+    let diff = run_diff(
+        worktree_path,
+        word_diff,
+        &["--stat", "--patch", &format!("{}...{}", main_branch, branch)],
+    )?;
+    if diff.is_empty() {
+        return Ok(format!("No changes since {}", main_branch));
+    }
+    Ok(diff)
+}
+
+/// Line numbers of the `@@` hunk headers in a diff
+pub fn hunk_starts(diff: &str) -> Vec<usize> {
+    diff.lines()
+        .enumerate()
+        .filter(|(_, line)| line.starts_with("@@"))
+        .map(|(i, _)| i)
+        .collect()
+}
+
+fn diff_command(worktree_path: &Path, word_diff: bool) -> Command {
+    let mut cmd = Command::new("git");
+    cmd.args(["diff", "--no-color", "--no-ext-diff"]);
+    if word_diff {
+        cmd.arg("--word-diff=plain");
+    }
+    cmd.current_dir(worktree_path);
+    cmd
+}
+
+fn run_diff(worktree_path: &Path, word_diff: bool, args: &[&str]) -> Result<String> {
+    let output = diff_command(worktree_path, word_diff).args(args).output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(anyhow!("git diff failed: {}", stderr));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
@@ -0,0 +1,90 @@
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::{anyhow, Result};
+
+use super::ChangedFile;
+
+/// Stage a file with unstaged changes, or unstage a fully staged one
+pub fn toggle_staged(worktree_path: &Path, file: &ChangedFile) -> Result<()> {
+    if file.unstaged != ' ' {
+        // `-A` also stages deletions
+        git(worktree_path, &["add", "-A", "--", &file.path])
+    } else {
+        git(worktree_path, &["restore", "--staged", "--", &file.path])
+    }
+}
+
+/// Throw away all changes to a file, staged and unstaged. Files that are new
+/// (untracked or only added to the index) are deleted, and a renamed file is
+/// moved back.
+pub fn discard_file(worktree_path: &Path, file: &ChangedFile) -> Result<()> {
+    match (file.staged, file.orig_path.as_deref()) {
+        ('?', _) => {}
+        // Restoring both paths from HEAD brings back the original and drops the new one
+        ('R', Some(orig)) => git(
+            worktree_path,
+            &["restore", "--staged", "--worktree", "--source=HEAD", "--", orig, &file.path],
+        )?,
+        ('A' | 'R' | 'C', _) => git(worktree_path, &["rm", "--cached", "-f", "-q", "--", &file.path])?,
+        _ => return git(worktree_path, &["checkout", "HEAD", "--", &file.path]),
+    }
+
+    let path = worktree_path.join(&file.path);
+    if path.exists() {
+        std::fs::remove_file(&path)?;
+    }
+    Ok(())
+}
+
+pub fn commit(worktree_path: &Path, message: &str) -> Result<()> {
+    git(worktree_path, &["commit", "-q", "-m", message])
+}
+
+fn git(worktree_path: &Path, args: &[&str]) -> Result<()> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(worktree_path)
+        .output()?;
+
+    if !output.status.success() {
+        // `git commit` reports "nothing to commit" on stdout
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let message = if stderr.trim().is_empty() { stdout } else { stderr };
+        let first_line = message.trim().lines().next().unwrap_or_default();
+        return Err(anyhow!("git {} failed: {}", args[0], first_line));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_discard_rename() {
+        let dir = std::env::temp_dir().join(format!("wtm-index-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let run = |args: &[&str]| {
+            git(&dir, &[&["-c", "user.name=t", "-c", "user.email=t@t"], args].concat()).unwrap()
+        };
+        run(&["init", "-q"]);
+        std::fs::write(dir.join("a.txt"), "a\n").unwrap();
+        run(&["add", "a.txt"]);
+        run(&["commit", "-q", "-m", "init"]);
+        run(&["mv", "a.txt", "b.txt"]);
+        std::fs::write(dir.join("b.txt"), "a\nmore\n").unwrap();
+
+        let files = crate::git::changed_files(&dir).unwrap();
+        assert_eq!(files[0].orig_path.as_deref(), Some("a.txt"));
+        discard_file(&dir, &files[0]).unwrap();
+
+        assert!(crate::git::changed_files(&dir).unwrap().is_empty());
+        assert_eq!(std::fs::read_to_string(dir.join("a.txt")).unwrap(), "a\n");
+        assert!(!dir.join("b.txt").exists());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,92 @@
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::{anyhow, Result};
+
+use super::worktree::find_main_branch;
+
+/// A commit in the branch log
+pub struct Commit {
+    pub hash: String,
+    pub short_hash: String,
+    pub author: String,
+    pub time: i64,
+    pub subject: String,
+}
+
+/// Commits on `branch` that aren't on the main branch, newest first. This is
+/// the "ahead" side of the count shown in the list.
+pub fn branch_log(worktree_path: &Path, branch: Option<&str>) -> Result<Vec<Commit>> {
+    let Some(branch) = branch else {
+        return Ok(Vec::new()); // Detached HEAD
+    };
+    let Some(main_branch) = find_main_branch(worktree_path) else {
+        return Ok(Vec::new());
+    };
+    if branch == main_branch {
+        return Ok(Vec::new());
+    }
+
+    let output = Command::new("git")
+        .args([
+            "log",
+            "--format=%H%x1f%h%x1f%an%x1f%ct%x1f%s",
+            &format!("{}..{}", main_branch, branch),
+        ])
+        .current_dir(worktree_path)
+        .output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(anyhow!("git log failed: {}", stderr));
+    }
+
+    Ok(parse_log(&String::from_utf8_lossy(&output.stdout)))
+}
+
+fn parse_log(output: &str) -> Vec<Commit> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(5, '\x1f');
+            Some(Commit {
+                hash: fields.next()?.to_string(),
+                short_hash: fields.next()?.to_string(),
+                author: fields.next()?.to_string(),
+                time: fields.next()?.parse().ok()?,
+                subject: fields.next()?.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Full commit message, stat and patch of a commit
+pub fn commit_diff(worktree_path: &Path, hash: &str) -> Result<String> {
+    let output = Command::new("git")
+        .args(["show", "--no-color", "--stat", "--patch", "--format=fuller", hash])
+        .current_dir(worktree_path)
+        .output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(anyhow!("git show failed: {}", stderr));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_log() {
+        let output = "abc123\x1fabc\x1fAlice\x1f1700000000\x1fAdd login\nbroken line\n";
+        let commits = parse_log(output);
+        assert_eq!(commits.len(), 1);
+        assert_eq!(commits[0].short_hash, "abc");
+        assert_eq!(commits[0].author, "Alice");
+        assert_eq!(commits[0].time, 1700000000);
+        assert_eq!(commits[0].subject, "Add login");
+    }
+}
@@ -1,6 +1,20 @@
+mod diff;
+mod index;
+mod log;
+mod operation;
+mod stash;
+mod status;
 mod worktree;
 
+pub use diff::{branch_diff, file_diff, hunk_starts};
+pub use index::{commit, discard_file, toggle_staged};
+pub use log::{branch_log, commit_diff, Commit};
+pub use operation::{abort_operation, continue_operation, detect_operation, Operation};
+pub use stash::{list_stashes, stash_apply, stash_diff, stash_drop, stash_push, Stash};
+pub use status::{changed_files, read_status, ChangedFile, StatusCounts};
 pub use worktree::{
-    create_worktree, delete_worktree, get_git_status, list_branches, list_worktrees,
-    merge_main_ff, Worktree, WorktreeStatus,
+    add_exclude, clone_bare, create_worktree, delete_worktree, find_main_branch, git_dir, git_dirs, list_branches,
+    list_worktrees, list_worktrees_reusing, main_commit, main_repo, merge_main_ff,
+    new_worktree_path, push_branch, reload_worktree, set_home_worktree, user_name, Worktree,
+    WorktreeStatus,
 };
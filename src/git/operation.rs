@@ -0,0 +1,116 @@
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+/// A multi-step git command that was started but not finished, usually
+/// because it stopped on a conflict
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Operation {
+    Rebase,
+    Am,
+    Merge,
+    CherryPick,
+    Revert,
+    Bisect,
+}
+
+impl Operation {
+    /// Badge shown in the list
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Rebase => "REBASING",
+            Self::Am => "APPLYING",
+            Self::Merge => "MERGING",
+            Self::CherryPick => "CHERRY-PICKING",
+            Self::Revert => "REVERTING",
+            Self::Bisect => "BISECTING",
+        }
+    }
+
+    /// The git subcommand, also used in messages ("a rebase is in progress")
+    pub fn command(self) -> &'static str {
+        match self {
+            Self::Rebase => "rebase",
+            Self::Am => "am",
+            Self::Merge => "merge",
+            Self::CherryPick => "cherry-pick",
+            Self::Revert => "revert",
+            Self::Bisect => "bisect",
+        }
+    }
+}
+
+/// Look for the state files git leaves in the worktree's git dir
+pub fn detect_operation(worktree_path: &Path) -> Option<Operation> {
+    let git_dir = super::git_dir(worktree_path)?;
+    let markers = [
+        ("rebase-merge", Operation::Rebase),
+        ("rebase-apply/applying", Operation::Am),
+        ("rebase-apply", Operation::Rebase),
+        ("MERGE_HEAD", Operation::Merge),
+        ("CHERRY_PICK_HEAD", Operation::CherryPick),
+        ("REVERT_HEAD", Operation::Revert),
+        ("BISECT_LOG", Operation::Bisect),
+    ];
+    markers
+        .into_iter()
+        .find(|(marker, _)| git_dir.join(marker).exists())
+        .map(|(_, op)| op)
+}
+
+/// Continue after conflicts were resolved, keeping git's default commit message
+pub fn continue_operation(worktree_path: &Path, op: Operation) -> Result<()> {
+    if op == Operation::Bisect {
+        return Err(anyhow!("A bisect continues with `git bisect good` or `git bisect bad`"));
+    }
+    run(worktree_path, &[op.command(), "--continue"])
+}
+
+pub fn abort_operation(worktree_path: &Path, op: Operation) -> Result<()> {
+    match op {
+        Operation::Bisect => run(worktree_path, &["bisect", "reset"]),
+        _ => run(worktree_path, &[op.command(), "--abort"]),
+    }
+}
+
+fn run(worktree_path: &Path, args: &[&str]) -> Result<()> {
+    let output = Command::new("git")
+        .args(args)
+        .env("GIT_EDITOR", "true") // don't open an editor inside the TUI
+        .current_dir(worktree_path)
+        .output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let message = if stderr.trim().is_empty() { stdout } else { stderr };
+        let first_line = message.trim().lines().next().unwrap_or_default();
+        return Err(anyhow!("git {} failed: {}", args.join(" "), first_line));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_operation() {
+        let dir = std::env::temp_dir().join(format!("wtm-op-test-{}", std::process::id()));
+        let git_dir = dir.join("gitdir");
+        std::fs::create_dir_all(git_dir.join("rebase-merge")).unwrap();
+        std::fs::write(dir.join(".git"), format!("gitdir: {}\n", git_dir.display())).unwrap();
+
+        assert_eq!(detect_operation(&dir), Some(Operation::Rebase));
+        std::fs::remove_dir(git_dir.join("rebase-merge")).unwrap();
+        assert_eq!(detect_operation(&dir), None);
+        std::fs::write(git_dir.join("CHERRY_PICK_HEAD"), "abc\n").unwrap();
+        assert_eq!(detect_operation(&dir), Some(Operation::CherryPick));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
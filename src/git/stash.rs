@@ -0,0 +1,137 @@
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::{anyhow, Result};
+use serde::Serialize;
+
+/// An entry of `git stash list`. Stashes are shared by all worktrees of a repo.
+#[derive(Debug, Clone, Serialize)]
+pub struct Stash {
+    pub name: String,           // "stash@{0}"
+    pub branch: Option<String>, // branch it was made on, None when detached
+    pub message: String,
+    pub time: i64,
+}
+
+pub fn list_stashes(repo_path: &Path) -> Vec<Stash> {
+    let output = Command::new("git")
+        .args(["stash", "list", "--format=%gd%x1f%ct%x1f%gs"])
+        .current_dir(repo_path)
+        .output();
+
+    match output {
+        Ok(out) if out.status.success() => parse_stash_list(&String::from_utf8_lossy(&out.stdout)),
+        _ => Vec::new(),
+    }
+}
+
+fn parse_stash_list(output: &str) -> Vec<Stash> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(3, '\x1f');
+            let name = fields.next()?.to_string();
+            let time = fields.next()?.parse().ok()?;
+            let subject = fields.next()?;
+
+            // "WIP on <branch>: <sha> <subject>" or "On <branch>: <message>"
+            let (branch, message) = subject
+                .strip_prefix("WIP on ")
+                .or_else(|| subject.strip_prefix("On "))
+                .and_then(|rest| rest.split_once(": "))
+                .map_or((None, subject), |(b, m)| (Some(b), m));
+            Some(Stash {
+                name,
+                branch: branch.filter(|b| *b != "(no branch)").map(str::to_string),
+                message: message.to_string(),
+                time,
+            })
+        })
+        .collect()
+}
+
+/// Stash all changes of a worktree, including untracked files but not the
+/// status file, so the notes stay put
+pub fn stash_push(worktree_path: &Path) -> Result<()> {
+    let exclude = format!(":(exclude){}", crate::status::STATUS_FILE);
+    git(worktree_path, &["stash", "push", "--include-untracked", "--", ".", &exclude])
+}
+
+/// Apply a stash to the worktree, dropping it afterwards when `pop` is set.
+/// What was staged is restored too, unless the index has moved on too far;
+/// then the changes are applied unstaged, like `git stash apply` does.
+pub fn stash_apply(worktree_path: &Path, name: &str, pop: bool) -> Result<()> {
+    let action = if pop { "pop" } else { "apply" };
+    let output = Command::new("git")
+        .args(["stash", action, "--index", name])
+        .current_dir(worktree_path)
+        .output()?;
+    if output.status.success() {
+        return Ok(());
+    }
+
+    // git leaves the worktree untouched when it gives up on the index
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if stderr.to_lowercase().contains("try without --index") {
+        return git(worktree_path, &["stash", action, name]);
+    }
+    let first_line = stderr.trim().lines().next().unwrap_or_default();
+    Err(anyhow!("git stash {} failed: {}", action, first_line))
+}
+
+pub fn stash_drop(worktree_path: &Path, name: &str) -> Result<()> {
+    git(worktree_path, &["stash", "drop", name])
+}
+
+/// Stat and patch of a stash, including untracked files
+pub fn stash_diff(repo_path: &Path, name: &str) -> Result<String> {
+    let output = Command::new("git")
+        .args(["stash", "show", "--no-color", "--stat", "--patch", "--include-untracked", name])
+        .current_dir(repo_path)
+        .output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(anyhow!("git stash show failed: {}", stderr));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+fn git(worktree_path: &Path, args: &[&str]) -> Result<()> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(worktree_path)
+        .output()?;
+
+    if !output.status.success() {
+        // Merge conflicts are reported on stdout
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let message = if stderr.trim().is_empty() { stdout } else { stderr };
+        let mut lines = message.trim().lines();
+        let line = lines.clone().find(|l| l.starts_with("CONFLICT")).or(lines.next());
+        return Err(anyhow!("git stash {} failed: {}", args[1], line.unwrap_or_default()));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_stash_list() {
+        let output = "stash@{0}\x1f1700000000\x1fOn feature/login: half done\n\
+                      stash@{1}\x1f1690000000\x1fWIP on main: abc1234 Initial commit\n\
+                      stash@{2}\x1f1680000000\x1fWIP on (no branch): abc1234 x\n";
+        let stashes = parse_stash_list(output);
+        assert_eq!(stashes.len(), 3);
+        assert_eq!(stashes[0].branch.as_deref(), Some("feature/login"));
+        assert_eq!(stashes[0].message, "half done");
+        assert_eq!(stashes[1].name, "stash@{1}");
+        assert_eq!(stashes[1].message, "abc1234 Initial commit");
+        assert_eq!(stashes[2].branch, None);
+    }
+}
@@ -0,0 +1,213 @@
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+use super::worktree::Upstream;
+
+/// Number of changed paths by kind, shown as `+2 ~3 »1 ?1 !1` in the list
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct StatusCounts {
+    pub staged: u32,
+    pub modified: u32, // changed in the worktree but not staged
+    pub renamed: u32,
+    pub untracked: u32,
+    pub conflicted: u32,
+}
+
+impl StatusCounts {
+    pub fn is_clean(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// A file with uncommitted changes
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChangedFile {
+    pub path: String,
+    pub staged: char,   // index status: 'M', 'A', 'D', 'R', ' ', '?'
+    pub unstaged: char, // worktree status
+    pub orig_path: Option<String>, // where a staged rename or copy came from
+}
+
+impl ChangedFile {
+    pub fn is_untracked(&self) -> bool {
+        self.staged == '?'
+    }
+
+    pub fn is_staged(&self) -> bool {
+        !matches!(self.staged, ' ' | '?')
+    }
+}
+
+/// Everything `git status --porcelain=v2 --branch` reports for a worktree
+#[derive(Default)]
+pub struct GitStatus {
+    pub counts: StatusCounts,
+    pub files: Vec<ChangedFile>,
+    pub upstream: Option<Upstream>,
+}
+
+pub fn read_status(worktree_path: &Path) -> Result<GitStatus> {
+    let output = Command::new("git")
+        .args(["status", "--porcelain=v2", "--branch", "--untracked-files=all", "-z"])
+        // Don't refresh the index, which would wake up the file watcher again
+        .env("GIT_OPTIONAL_LOCKS", "0")
+        .current_dir(worktree_path)
+        .output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(anyhow!("git status failed: {}", stderr));
+    }
+
+    Ok(parse_status(&String::from_utf8_lossy(&output.stdout)))
+}
+
+pub fn changed_files(worktree_path: &Path) -> Result<Vec<ChangedFile>> {
+    Ok(read_status(worktree_path)?.files)
+}
+
+/// Parse NUL-separated porcelain v2 records. Renames carry the original path
+/// in an extra record.
+fn parse_status(output: &str) -> GitStatus {
+    let mut status = GitStatus::default();
+    let mut upstream_name = None;
+    let mut ahead_behind = None;
+
+    let mut records = output.split('\0').filter(|r| !r.is_empty());
+    while let Some(record) = records.next() {
+        let (kind, rest) = record.split_once(' ').unwrap_or((record, ""));
+        match kind {
+            "#" => {
+                if let Some(name) = rest.strip_prefix("branch.upstream ") {
+                    upstream_name = Some(name.to_string());
+                } else if let Some(ab) = rest.strip_prefix("branch.ab ") {
+                    // "+<ahead> -<behind>"
+                    let mut counts = ab
+                        .split(' ')
+                        .map(|n| n.strip_prefix(['+', '-']).and_then(|n| n.parse().ok()).unwrap_or(0));
+                    ahead_behind = Some((counts.next().unwrap_or(0), counts.next().unwrap_or(0)));
+                }
+            }
+            "1" | "2" => {
+                // "<XY> <sub> <mH> <mI> <mW> <hH> <hI> [<score>] <path>"
+                let fields = if kind == "1" { 8 } else { 9 };
+                let parts: Vec<&str> = rest.splitn(fields, ' ').collect();
+                let Some(path) = parts.get(fields - 1) else {
+                    continue;
+                };
+                let mut xy = parts[0].chars();
+                let staged = xy.next().unwrap_or('.');
+                let unstaged = xy.next().unwrap_or('.');
+
+                let mut file = changed_file(path, staged, unstaged);
+                if kind == "2" {
+                    status.counts.renamed += 1;
+                    file.orig_path = records.next().map(str::to_string);
+                } else if staged != '.' {
+                    status.counts.staged += 1;
+                }
+                if unstaged != '.' {
+                    status.counts.modified += 1;
+                }
+                status.files.push(file);
+            }
+            "u" => {
+                // "<XY> <sub> <m1> <m2> <m3> <mW> <h1> <h2> <h3> <path>"
+                status.counts.conflicted += 1;
+                if let Some(path) = rest.splitn(10, ' ').nth(9) {
+                    let mut xy = rest.chars();
+                    let staged = xy.next().unwrap_or('U');
+                    let unstaged = xy.next().unwrap_or('U');
+                    status.files.push(changed_file(path, staged, unstaged));
+                }
+            }
+            "?" => {
+                status.counts.untracked += 1;
+                status.files.push(changed_file(rest, '?', '?'));
+            }
+            _ => {} // "!" ignored files
+        }
+    }
+
+    // An upstream without ahead/behind counts no longer exists on the remote
+    status.upstream = upstream_name.map(|name| {
+        let (ahead, behind) = ahead_behind.unwrap_or((0, 0));
+        Upstream {
+            name,
+            ahead,
+            behind,
+            gone: ahead_behind.is_none(),
+        }
+    });
+    status
+}
+
+fn changed_file(path: &str, staged: char, unstaged: char) -> ChangedFile {
+    let column = |c: char| if c == '.' { ' ' } else { c };
+    ChangedFile {
+        path: path.to_string(),
+        staged: column(staged),
+        unstaged: column(unstaged),
+        orig_path: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_status() {
+        let output = [
+            "# branch.oid abc",
+            "# branch.head feature",
+            "# branch.upstream origin/feature",
+            "# branch.ab +2 -1",
+            "1 .M N... 100644 100644 100644 abc abc src/app.rs",
+            "1 MM N... 100644 100644 100644 abc abc README.md",
+            "2 R. N... 100644 100644 100644 abc abc R100 new name.rs",
+            "old.rs",
+            "u UU N... 100644 100644 100644 100644 abc abc abc conflict.rs",
+            "? with space.txt",
+            "",
+        ]
+        .join("\0");
+        let status = parse_status(&output);
+
+        let counts = status.counts;
+        assert_eq!(
+            (counts.staged, counts.modified, counts.renamed, counts.untracked, counts.conflicted),
+            (1, 2, 1, 1, 1)
+        );
+        assert_eq!(status.files.len(), 5);
+        assert_eq!((status.files[0].staged, status.files[0].unstaged), (' ', 'M'));
+        assert_eq!(status.files[2].path, "new name.rs");
+        assert_eq!(status.files[2].orig_path.as_deref(), Some("old.rs"));
+        assert_eq!(status.files[3].path, "conflict.rs");
+        assert!(status.files[4].is_untracked());
+        assert_eq!(status.files[4].path, "with space.txt");
+
+        let upstream = status.upstream.unwrap();
+        assert_eq!((upstream.name.as_str(), upstream.ahead, upstream.behind), ("origin/feature", 2, 1));
+        assert!(!upstream.gone);
+    }
+
+    #[test]
+    fn test_parse_status_upstream() {
+        // Configured upstream deleted on the remote: no branch.ab line
+        let status = parse_status("# branch.head old\0# branch.upstream origin/old\0");
+        assert!(status.upstream.unwrap().gone);
+        assert!(status.counts.is_clean());
+
+        let status = parse_status("# branch.head local\0");
+        assert!(status.upstream.is_none());
+
+        // Malformed counts don't panic
+        let status = parse_status("# branch.upstream origin/x\0# branch.ab  +\0");
+        let upstream = status.upstream.unwrap();
+        assert_eq!((upstream.ahead, upstream.behind), (0, 0));
+    }
+}
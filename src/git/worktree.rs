@@ -2,27 +2,81 @@ use std::path::{Path, PathBuf};
 use std::process::Command;
 
 use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
 
-#[derive(Default)]
+use super::{ChangedFile, Operation, Stash, StatusCounts};
+use crate::config::NotesStorage;
+use crate::status::{Frontmatter, Link, Section};
+
+#[derive(Default, Clone, Serialize, Deserialize)]
 pub struct WorktreeStatus {
     pub purpose: Option<String>,
     pub progress: (u32, u32), // (checked, total)
+    pub has_file: bool,
+    pub meta: Frontmatter,
+    pub sections: Vec<Section>,
+    pub links: Vec<Link>,
+}
+
+/// Remote-tracking information for a branch
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Upstream {
+    pub name: String, // e.g. "origin/feature"
+    pub ahead: u32,
+    pub behind: u32,
+    pub gone: bool, // configured upstream was deleted on the remote
 }
 
+#[derive(Default, Clone, Serialize, Deserialize)]
 pub struct Worktree {
     pub path: PathBuf,
+    pub repo: PathBuf, // the repository's main worktree (or bare dir)
     pub branch: Option<String>,
     pub commit: String,
-    pub is_main: bool,
-    #[allow(dead_code)]
-    pub is_bare: bool,
+    pub is_main: bool, // the home worktree: listed first, never deleted
+    pub is_bare: bool, // the bare repository itself, which has no checkout
     pub has_changes: bool,
+    pub changes: StatusCounts,
+    pub operation: Option<Operation>, // rebase, merge, ... stopped halfway
     pub status: WorktreeStatus,
     pub ahead: u32,
     pub behind: u32,
+    pub upstream: Option<Upstream>,
+    pub stashes: usize, // stashes made on this branch
+    pub last_commit_time: Option<i64>, // unix seconds
+    pub last_modified: Option<i64>,    // unix seconds, newest of index and dirty files
+    pub last_entered: Option<i64>,     // unix seconds, filled in from wtm's state file
+    pub frecency: f64,                 // filled in from wtm's state file
+}
+
+impl Worktree {
+    /// Merged and ready to delete: nothing ahead of main, clean, not main itself
+    pub fn is_merged(&self) -> bool {
+        !self.is_main && !self.is_bare && self.ahead == 0 && !self.has_changes
+    }
+
+    /// Most recent of last commit, last modification and last entered
+    pub fn last_activity(&self) -> Option<i64> {
+        [self.last_commit_time, self.last_modified, self.last_entered]
+            .into_iter()
+            .flatten()
+            .max()
+    }
+}
+
+pub fn list_worktrees(repo_path: &Path, notes: NotesStorage) -> Result<Vec<Worktree>> {
+    list_worktrees_reusing(repo_path, notes, |_, _, _| None)
 }
 
-pub fn list_worktrees(repo_path: &Path) -> Result<Vec<Worktree>> {
+/// Like `list_worktrees`, but takes a worktree's metadata from `reuse` (called
+/// with its path, branch and HEAD) instead of querying git when it has some.
+/// Operations in progress, stash counts and uncommitted changes are always
+/// re-read.
+pub fn list_worktrees_reusing(
+    repo_path: &Path,
+    notes: NotesStorage,
+    mut reuse: impl FnMut(&Path, Option<&str>, &str) -> Option<Worktree>,
+) -> Result<Vec<Worktree>> {
     let output = Command::new("git")
         .args(["worktree", "list", "--porcelain"])
         .current_dir(repo_path)
@@ -33,92 +87,232 @@ pub fn list_worktrees(repo_path: &Path) -> Result<Vec<Worktree>> {
         return Err(anyhow!("git worktree list failed: {}", stderr));
     }
 
-    // Find main branch once for all worktrees
+    // Find main branch and stashes once for all worktrees
     let main_branch = find_main_branch(repo_path);
+    let stashes = super::list_stashes(repo_path);
 
     let stdout = String::from_utf8_lossy(&output.stdout);
-    parse_worktree_list(&stdout, main_branch.as_deref())
+    let mut entries = parse_worktree_list(&stdout);
+    let repo = entries.first().map_or_else(|| repo_path.to_path_buf(), |e| e.path.clone());
+    mark_home(&mut entries, home_worktree(repo_path).as_deref(), main_branch.as_deref());
+    let worktrees = entries
+        .into_iter()
+        .map(|entry| match reuse(&entry.path, entry.branch.as_deref(), &entry.commit) {
+            Some(mut wt) => {
+                wt.is_main = entry.is_main;
+                wt.operation = super::detect_operation(&wt.path);
+                wt.stashes = count_stashes(&stashes, wt.branch.as_deref());
+                // New untracked files and edits don't necessarily touch the index
+                if !wt.is_bare {
+                    let git_status = super::read_status(&wt.path).unwrap_or_default();
+                    wt.has_changes = !git_status.counts.is_clean();
+                    wt.changes = git_status.counts;
+                    wt.upstream = git_status.upstream;
+                    wt.last_modified = get_last_modified(&wt.path, &git_status.files);
+                }
+                wt
+            }
+            None => load_worktree(entry, &repo, main_branch.as_deref(), &stashes, notes),
+        })
+        .collect();
+
+    Ok(worktrees)
+}
+
+/// Re-read a single worktree after something changed on disk, without
+/// listing all of them again. Visit history is left to the caller.
+pub fn reload_worktree(wt: &Worktree, notes: NotesStorage) -> Result<Worktree> {
+    let output = Command::new("git")
+        .args(["rev-parse", "HEAD"])
+        .current_dir(&wt.path)
+        .output()?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(anyhow!("git rev-parse failed: {}", stderr));
+    }
+    let commit = String::from_utf8_lossy(&output.stdout).trim().chars().take(7).collect();
+
+    // The branch can change through a checkout in that worktree
+    let branch = Command::new("git")
+        .args(["symbolic-ref", "--short", "-q", "HEAD"])
+        .current_dir(&wt.path)
+        .output()
+        .ok()
+        .filter(|out| out.status.success())
+        .map(|out| String::from_utf8_lossy(&out.stdout).trim().to_string());
+
+    let entry = WorktreeEntry {
+        path: wt.path.clone(),
+        branch,
+        commit,
+        is_main: wt.is_main,
+        is_bare: wt.is_bare,
+    };
+    let main_branch = find_main_branch(&wt.path);
+    let stashes = super::list_stashes(&wt.path);
+    Ok(load_worktree(entry, &wt.repo, main_branch.as_deref(), &stashes, notes))
+}
+
+/// Run the per-worktree git queries for an entry
+fn load_worktree(
+    entry: WorktreeEntry,
+    repo: &Path,
+    main_branch: Option<&str>,
+    stashes: &[Stash],
+    notes: NotesStorage,
+) -> Worktree {
+    // Nothing checked out, so nothing to ask git about
+    if entry.is_bare {
+        return Worktree {
+            path: entry.path,
+            repo: repo.to_path_buf(),
+            commit: entry.commit,
+            is_bare: true,
+            ..Default::default()
+        };
+    }
+
+    let git_status = super::read_status(&entry.path).unwrap_or_default();
+    let operation = super::detect_operation(&entry.path);
+    let status = load_worktree_status(&entry.path, entry.branch.as_deref(), notes);
+    let branch_ref = entry.branch.as_deref();
+    let (ahead, behind) = get_ahead_behind(&entry.path, branch_ref, main_branch);
+    let stashes = count_stashes(stashes, branch_ref);
+    let last_commit_time = get_last_commit_time(&entry.path);
+    let last_modified = get_last_modified(&entry.path, &git_status.files);
+    Worktree {
+        path: entry.path,
+        repo: repo.to_path_buf(),
+        branch: entry.branch,
+        commit: entry.commit,
+        is_main: entry.is_main,
+        is_bare: entry.is_bare,
+        has_changes: !git_status.counts.is_clean(),
+        changes: git_status.counts,
+        operation,
+        status,
+        ahead,
+        behind,
+        upstream: git_status.upstream,
+        stashes,
+        last_commit_time,
+        last_modified,
+        last_entered: None,
+        frecency: 0.0,
+    }
+}
+
+fn count_stashes(stashes: &[Stash], branch: Option<&str>) -> usize {
+    stashes
+        .iter()
+        .filter(|s| branch.is_some() && s.branch.as_deref() == branch)
+        .count()
+}
+
+/// A worktree as reported by `git worktree list --porcelain`, before any
+/// per-worktree git queries are made
+struct WorktreeEntry {
+    path: PathBuf,
+    branch: Option<String>,
+    commit: String,
+    is_main: bool,
+    is_bare: bool,
 }
 
-fn parse_worktree_list(output: &str, main_branch: Option<&str>) -> Result<Vec<Worktree>> {
-    let mut worktrees = Vec::new();
-    let mut current_path: Option<PathBuf> = None;
-    let mut current_commit = String::new();
-    let mut current_branch: Option<String> = None;
-    let mut is_bare = false;
+fn parse_worktree_list(output: &str) -> Vec<WorktreeEntry> {
+    let mut entries: Vec<WorktreeEntry> = Vec::new();
 
     for line in output.lines() {
-        if line.starts_with("worktree ") {
-            // Save previous worktree if exists
-            if let Some(path) = current_path.take() {
-                let has_changes = has_uncommitted_changes(&path).unwrap_or(false);
-                let status = load_worktree_status(&path);
-                let branch_ref = current_branch.as_deref();
-                let (ahead, behind) = get_ahead_behind(&path, branch_ref, main_branch);
-                worktrees.push(Worktree {
-                    path,
-                    branch: current_branch.take(),
-                    commit: std::mem::take(&mut current_commit),
-                    is_main: worktrees.is_empty(),
-                    is_bare,
-                    has_changes,
-                    status,
-                    ahead,
-                    behind,
-                });
-                is_bare = false;
-            }
-            current_path = Some(PathBuf::from(&line[9..]));
-        } else if line.starts_with("HEAD ") {
+        if let Some(path) = line.strip_prefix("worktree ") {
+            entries.push(WorktreeEntry {
+                path: PathBuf::from(path),
+                branch: None,
+                commit: String::new(),
+                is_main: entries.is_empty(),
+                is_bare: false,
+            });
+            continue;
+        }
+
+        let Some(entry) = entries.last_mut() else {
+            continue;
+        };
+
+        if let Some(head) = line.strip_prefix("HEAD ") {
             // Take first 7 chars for short hash
-            current_commit = line[5..].chars().take(7).collect();
-        } else if line.starts_with("branch ") {
-            let branch = &line[7..];
-            current_branch = Some(
+            entry.commit = head.chars().take(7).collect();
+        } else if let Some(branch) = line.strip_prefix("branch ") {
+            entry.branch = Some(
                 branch
                     .strip_prefix("refs/heads/")
                     .unwrap_or(branch)
                     .to_string(),
             );
         } else if line == "bare" {
-            is_bare = true;
+            entry.is_bare = true;
+            entry.is_main = false;
         }
     }
 
-    // Don't forget the last worktree
-    if let Some(path) = current_path {
-        let has_changes = has_uncommitted_changes(&path).unwrap_or(false);
-        let status = load_worktree_status(&path);
-        let branch_ref = current_branch.as_deref();
-        let (ahead, behind) = get_ahead_behind(&path, branch_ref, main_branch);
-        worktrees.push(Worktree {
-            path,
-            branch: current_branch,
-            commit: current_commit,
-            is_main: worktrees.is_empty(),
-            is_bare,
-            has_changes,
-            status,
-            ahead,
-            behind,
+    entries
+}
+
+/// Pick the home worktree: the main checkout, else (in a bare repository) the
+/// one set with `set_home_worktree`, else the one on the main branch
+fn mark_home(entries: &mut [WorktreeEntry], home: Option<&Path>, main_branch: Option<&str>) {
+    // git lists the main checkout first, or the bare repository in its place
+    let has_checkout = entries.first().is_some_and(|e| !e.is_bare);
+    let index = has_checkout
+        .then_some(0)
+        .or_else(|| home.and_then(|home| entries.iter().position(|e| e.path == home && !e.is_bare)))
+        .or_else(|| {
+            entries
+                .iter()
+                .position(|e| main_branch.is_some() && e.branch.as_deref() == main_branch)
         });
+    for (i, entry) in entries.iter_mut().enumerate() {
+        entry.is_main = Some(i) == index;
     }
+}
 
-    Ok(worktrees)
+/// Home worktree chosen with `set_home_worktree`, from `wtm.home` in the repo config
+fn home_worktree(repo_path: &Path) -> Option<PathBuf> {
+    let output = Command::new("git")
+        .args(["config", "--get", "wtm.home"])
+        .current_dir(repo_path)
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| PathBuf::from(String::from_utf8_lossy(&output.stdout).trim()))
 }
 
-fn load_worktree_status(path: &Path) -> WorktreeStatus {
-    let status_path = path.join(".worktree-status.md");
-    if !status_path.exists() {
-        return WorktreeStatus::default();
+/// Make a worktree the repository's home one, listed first in place of the
+/// main checkout (bare repositories have none)
+pub fn set_home_worktree(repo_path: &Path, worktree_path: &Path) -> Result<()> {
+    let output = Command::new("git")
+        .args(["config", "wtm.home"])
+        .arg(worktree_path)
+        .current_dir(repo_path)
+        .output()?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(anyhow!("git config failed: {}", stderr.trim()));
     }
+    Ok(())
+}
 
+fn load_worktree_status(path: &Path, branch: Option<&str>, notes: NotesStorage) -> WorktreeStatus {
+    let status_path = crate::status::status_path(notes, path, branch);
     let content = match std::fs::read_to_string(&status_path) {
         Ok(c) => c,
         Err(_) => return WorktreeStatus::default(),
     };
 
-    crate::status::parse_status_file(&content)
+    let mut status = crate::status::parse_status_file(&content);
+    status.has_file = true;
+    status
 }
 
 pub fn list_branches(repo_path: &Path) -> Result<Vec<String>> {
@@ -187,6 +381,71 @@ pub fn create_worktree(
     Ok(())
 }
 
+/// Where a new worktree for `branch` goes: next to the main checkout as
+/// `<repo>-<branch>`, or inside the directory holding a hidden bare repository
+/// (`project/.bare`) as `project/<branch>`
+pub fn new_worktree_path(repo_path: &Path, branch: &str) -> PathBuf {
+    let branch = branch.replace('/', "-");
+    let parent = repo_path.parent().unwrap_or(repo_path);
+    let name = repo_path.file_name().and_then(|n| n.to_str()).unwrap_or("repo");
+    if name.starts_with('.') {
+        parent.join(branch)
+    } else {
+        parent.join(format!("{}-{}", name.trim_end_matches(".git"), branch))
+    }
+}
+
+/// Clone `source` into the bare layout: `<dir>/.bare` holds the repository,
+/// `<dir>/.git` points at it so git works anywhere in `<dir>`, and the
+/// default branch is checked out in `<dir>/<branch>` as the home worktree.
+/// Returns the home worktree's path.
+pub fn clone_bare(source: &str, dir: Option<&Path>, cwd: &Path) -> Result<PathBuf> {
+    let dir = match dir {
+        Some(dir) => cwd.join(dir),
+        None => {
+            // Named after the repository, like git clone does
+            let name = source.trim_end_matches('/').rsplit(['/', ':']).next().unwrap_or(source);
+            cwd.join(name.trim_end_matches(".git"))
+        }
+    };
+    if dir.exists() {
+        return Err(anyhow!("{} already exists", dir.display()));
+    }
+    std::fs::create_dir_all(&dir)?;
+    let dir = std::fs::canonicalize(&dir)?;
+    let bare = dir.join(".bare");
+
+    let git = |args: &[&str], cwd: &Path| -> Result<String> {
+        let output = Command::new("git").args(args).current_dir(cwd).output()?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(anyhow!("git {} failed: {}", args[0], stderr.trim()));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+
+    let setup = || -> Result<PathBuf> {
+        git(&["clone", "--bare", source, ".bare"], &dir)?;
+        std::fs::write(dir.join(".git"), "gitdir: ./.bare\n")?;
+        // A bare clone doesn't track the remote's branches, so fetching wouldn't
+        // update anything and merge_main would have nothing to merge
+        git(&["config", "remote.origin.fetch", "+refs/heads/*:refs/remotes/origin/*"], &bare)?;
+        git(&["fetch", "-q", "origin"], &bare)?;
+
+        let branch = git(&["symbolic-ref", "--short", "HEAD"], &bare)?;
+        let home = new_worktree_path(&bare, &branch);
+        git(&["worktree", "add", "-q", &home.to_string_lossy(), &branch], &bare)?;
+        let upstream = format!("origin/{}", branch);
+        let _ = git(&["branch", "-q", "--set-upstream-to", &upstream, &branch], &bare);
+        set_home_worktree(&bare, &home)?;
+        Ok(home)
+    };
+    // Don't leave a half-made layout behind
+    setup().inspect_err(|_| {
+        let _ = std::fs::remove_dir_all(&dir);
+    })
+}
+
 pub fn delete_worktree(repo_path: &Path, worktree_path: &Path, force: bool) -> Result<()> {
     let mut args = vec!["worktree", "remove"];
     if force {
@@ -204,36 +463,32 @@ pub fn delete_worktree(repo_path: &Path, worktree_path: &Path, force: bool) -> R
     Ok(())
 }
 
-pub fn has_uncommitted_changes(worktree_path: &Path) -> Result<bool> {
+fn get_last_commit_time(worktree_path: &Path) -> Option<i64> {
     let output = Command::new("git")
-        .args(["status", "--porcelain"])
+        .args(["log", "-1", "--format=%ct", "HEAD"])
         .current_dir(worktree_path)
-        .output()?;
+        .output()
+        .ok()?;
 
     if !output.status.success() {
-        return Ok(false);
+        return None;
     }
 
-    Ok(!output.stdout.is_empty())
+    String::from_utf8_lossy(&output.stdout).trim().parse().ok()
 }
 
-pub fn get_git_status(worktree_path: &Path) -> Result<String> {
-    let output = Command::new("git")
-        .args(["status", "--short"])
-        .current_dir(worktree_path)
-        .output()?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(anyhow!("git status failed: {}", stderr));
-    }
-
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    if stdout.is_empty() {
-        Ok("Working tree clean".to_string())
-    } else {
-        Ok(stdout.to_string())
-    }
+/// Most recent modification in a worktree: the index (touched by checkout,
+/// add and commit) or any of the changed files `git status` listed, whichever
+/// is newer
+fn get_last_modified(worktree_path: &Path, files: &[ChangedFile]) -> Option<i64> {
+    let index = git_dir(worktree_path).map(|dir| dir.join("index"));
+    index
+        .into_iter()
+        .chain(files.iter().map(|f| worktree_path.join(&f.path)))
+        .filter_map(|p| std::fs::metadata(p).and_then(|m| m.modified()).ok())
+        .max()
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|d| d.as_secs() as i64)
 }
 
 /// Get commits ahead/behind compared to a base branch (main/master)
@@ -281,7 +536,7 @@ fn get_ahead_behind(worktree_path: &Path, branch: Option<&str>, main_branch: Opt
     }
 }
 
-fn find_main_branch(repo_path: &Path) -> Option<String> {
+pub fn find_main_branch(repo_path: &Path) -> Option<String> {
     // Check for common main branch names
     for name in &["main", "master"] {
         let output = Command::new("git")
@@ -298,6 +553,20 @@ fn find_main_branch(repo_path: &Path) -> Option<String> {
     None
 }
 
+/// Commit at the tip of main/master, which ahead/behind counts are relative to
+pub fn main_commit(repo_path: &Path) -> Option<String> {
+    let main_branch = find_main_branch(repo_path)?;
+    let output = Command::new("git")
+        .args(["rev-parse", "--verify", "-q", &format!("refs/heads/{}", main_branch)])
+        .current_dir(repo_path)
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
 /// Merge main/master into current branch using fast-forward only
 /// Fetches from origin first to ensure we have the latest
 pub fn merge_main_ff(worktree_path: &Path) -> Result<()> {
@@ -310,9 +579,19 @@ pub fn merge_main_ff(worktree_path: &Path) -> Result<()> {
         .current_dir(worktree_path)
         .output();
 
+    // Bare clones made without a fetch refspec have no origin/* refs, their
+    // local branches are what was cloned
+    let remote_main = format!("origin/{}", main_branch);
+    let has_remote_main = Command::new("git")
+        .args(["rev-parse", "--verify", "-q", &format!("refs/remotes/{}", remote_main)])
+        .current_dir(worktree_path)
+        .output()
+        .is_ok_and(|out| out.status.success());
+    let target = if has_remote_main { &remote_main } else { &main_branch };
+
     // Merge with ff-only
     let output = Command::new("git")
-        .args(["merge", "--ff-only", &format!("origin/{}", main_branch)])
+        .args(["merge", "--ff-only", target])
         .current_dir(worktree_path)
         .output()?;
 
@@ -323,3 +602,213 @@ pub fn merge_main_ff(worktree_path: &Path) -> Result<()> {
 
     Ok(())
 }
+
+/// Push the branch checked out in a worktree
+/// Sets the upstream on first push (to the first configured remote, usually origin)
+pub fn push_branch(worktree_path: &Path, branch: &str, has_upstream: bool) -> Result<()> {
+    let output = if has_upstream {
+        Command::new("git")
+            .args(["push"])
+            .current_dir(worktree_path)
+            .output()?
+    } else {
+        let remote = default_remote(worktree_path)
+            .ok_or_else(|| anyhow!("No remote configured"))?;
+        Command::new("git")
+            .args(["push", "--set-upstream", &remote, branch])
+            .current_dir(worktree_path)
+            .output()?
+    };
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(anyhow!("git push failed: {}", stderr.trim()));
+    }
+
+    Ok(())
+}
+
+/// Absolute (git dir, common dir) of a worktree: `.git/worktrees/<name>` and
+/// `.git` for a linked worktree, both `.git` for the main one
+pub fn git_dirs(worktree_path: &Path) -> Option<(PathBuf, PathBuf)> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--path-format=absolute", "--git-dir", "--git-common-dir"])
+        .current_dir(worktree_path)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut lines = stdout.lines();
+    Some((PathBuf::from(lines.next()?), PathBuf::from(lines.next()?)))
+}
+
+/// The repository a path is in: its main worktree, or the repository itself
+/// when it's bare. Works from any subdirectory of any of its worktrees.
+pub fn main_repo(path: &Path) -> Result<PathBuf> {
+    let (_, common_dir) =
+        git_dirs(path).ok_or_else(|| anyhow!("{} is not in a git repository", path.display()))?;
+    match common_dir.parent() {
+        Some(main) if common_dir.file_name().is_some_and(|n| n == ".git") => Ok(main.to_path_buf()),
+        _ => Ok(common_dir),
+    }
+}
+
+/// The worktree's git dir, read from `.git` without spawning git: a directory
+/// for the main worktree, a `gitdir: <path>` file for linked ones
+pub fn git_dir(worktree_path: &Path) -> Option<PathBuf> {
+    let dot_git = worktree_path.join(".git");
+    if dot_git.is_dir() {
+        return Some(dot_git);
+    }
+    let content = std::fs::read_to_string(&dot_git).ok()?;
+    let dir = content.trim().strip_prefix("gitdir: ")?;
+    Some(worktree_path.join(dir)) // join keeps absolute paths as they are
+}
+
+/// Add a pattern to the repo's `info/exclude` (shared by all worktrees) if missing
+pub fn add_exclude(repo_path: &Path, pattern: &str) -> Result<()> {
+    let (_, common_dir) =
+        git_dirs(repo_path).ok_or_else(|| anyhow!("Not a git repository"))?;
+    let exclude_path = common_dir.join("info").join("exclude");
+
+    let existing = std::fs::read_to_string(&exclude_path).unwrap_or_default();
+    if existing.lines().any(|l| l.trim() == pattern) {
+        return Ok(());
+    }
+
+    if let Some(parent) = exclude_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut content = existing;
+    if !content.is_empty() && !content.ends_with('\n') {
+        content.push('\n');
+    }
+    content.push_str(pattern);
+    content.push('\n');
+    std::fs::write(&exclude_path, content)?;
+    Ok(())
+}
+
+/// Author name as git would record it (config or `GIT_AUTHOR_NAME`), for templates
+pub fn user_name(repo_path: &Path) -> Option<String> {
+    let output = Command::new("git")
+        .args(["var", "GIT_AUTHOR_IDENT"])
+        .current_dir(repo_path)
+        .output()
+        .ok()?;
+
+    // "Name <email> timestamp tz"
+    let ident = String::from_utf8_lossy(&output.stdout);
+    let name = ident.split(" <").next()?.trim();
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+fn default_remote(repo_path: &Path) -> Option<String> {
+    let output = Command::new("git")
+        .args(["remote"])
+        .current_dir(repo_path)
+        .output()
+        .ok()?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let remotes: Vec<&str> = stdout.lines().collect();
+    if remotes.contains(&"origin") {
+        Some("origin".to_string())
+    } else {
+        remotes.first().map(|r| r.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_worktree_list() {
+        let output = "worktree /repo
+HEAD 1234567890abcdef
+branch refs/heads/main
+
+worktree /repo-feature
+HEAD abcdef1234567890
+detached
+";
+        let entries = parse_worktree_list(output);
+        assert_eq!(entries.len(), 2);
+        assert!(entries[0].is_main);
+        assert_eq!(entries[0].branch.as_deref(), Some("main"));
+        assert_eq!(entries[1].commit, "abcdef1");
+        assert_eq!(entries[1].branch, None);
+    }
+
+    #[test]
+    fn test_bare_repo_home() {
+        let output = "worktree /src/app/.bare
+bare
+
+worktree /src/app/main
+HEAD 1234567890abcdef
+branch refs/heads/main
+
+worktree /src/app/feature
+HEAD abcdef1234567890
+branch refs/heads/feature
+";
+        let mut entries = parse_worktree_list(output);
+        assert!(entries[0].is_bare && !entries[0].is_main);
+
+        mark_home(&mut entries, None, Some("main"));
+        assert!(entries[1].is_main);
+        mark_home(&mut entries, Some(Path::new("/src/app/feature")), Some("main"));
+        assert!(!entries[1].is_main && entries[2].is_main);
+
+        // A regular repository's main checkout stays home whatever is configured
+        let mut entries = parse_worktree_list(&output.replace("worktree /src/app/.bare\nbare\n\n", ""));
+        mark_home(&mut entries, Some(Path::new("/src/app/feature")), Some("main"));
+        assert!(entries[0].is_main && !entries[1].is_main);
+
+        assert_eq!(
+            new_worktree_path(Path::new("/src/app/.bare"), "fix/crash"),
+            PathBuf::from("/src/app/fix-crash")
+        );
+        assert_eq!(
+            new_worktree_path(Path::new("/src/app.git"), "fix/crash"),
+            PathBuf::from("/src/app-fix-crash")
+        );
+        assert_eq!(
+            new_worktree_path(Path::new("/src/app"), "fix/crash"),
+            PathBuf::from("/src/app-fix-crash")
+        );
+    }
+
+    #[test]
+    fn test_main_repo_from_linked_worktree_subdir() {
+        let dir = std::env::temp_dir().join(format!("wtm-main-repo-test-{}", std::process::id()));
+        let repo = dir.join("repo");
+        std::fs::create_dir_all(&repo).unwrap();
+        let git = |args: &[&str]| {
+            let status = Command::new("git")
+                .args(["-c", "user.name=t", "-c", "user.email=t@t"])
+                .args(args)
+                .current_dir(&repo)
+                .output()
+                .unwrap()
+                .status;
+            assert!(status.success(), "git {:?}", args);
+        };
+        git(&["init", "-q"]);
+        git(&["commit", "-q", "--allow-empty", "-m", "init"]);
+        git(&["worktree", "add", "-q", "-b", "feature", "../repo-feature"]);
+        let subdir = dir.join("repo-feature").join("src");
+        std::fs::create_dir_all(&subdir).unwrap();
+
+        let repo = std::fs::canonicalize(&repo).unwrap();
+        assert_eq!(main_repo(&subdir).unwrap(), repo);
+        assert_eq!(main_repo(&repo).unwrap(), repo);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
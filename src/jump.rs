@@ -0,0 +1,83 @@
+use crate::git::Worktree;
+
+/// Pick the worktree that best matches a fuzzy query.
+/// Match quality decides first; frecency breaks ties, so `wtm jump api`
+/// prefers the `api` worktree you actually use. An empty query returns
+/// the most frecent worktree. A bare repository is never picked, it has
+/// no checkout to cd into.
+pub fn best_match<'a>(worktrees: &'a [Worktree], query: &str) -> Option<&'a Worktree> {
+    let query = query.to_lowercase();
+
+    worktrees
+        .iter()
+        .filter(|wt| !wt.is_bare)
+        .filter_map(|wt| worktree_score(wt, &query).map(|score| (score, wt)))
+        .max_by(|(a, wa), (b, wb)| a.cmp(b).then(wa.frecency.total_cmp(&wb.frecency)))
+        .map(|(_, wt)| wt)
+}
+
+fn worktree_score(wt: &Worktree, query: &str) -> Option<u32> {
+    let dir_name = wt
+        .path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    let branch = wt.branch.as_deref().unwrap_or("").to_lowercase();
+
+    [match_score(&branch, query), match_score(&dir_name, query)]
+        .into_iter()
+        .flatten()
+        .max()
+}
+
+/// Score how well `candidate` matches `query` (both lowercase).
+/// Exact > suffix > substring at a word boundary > substring > subsequence.
+fn match_score(candidate: &str, query: &str) -> Option<u32> {
+    if query.is_empty() {
+        return Some(0);
+    }
+    if candidate == query {
+        return Some(1000);
+    }
+    if candidate.ends_with(query) {
+        return Some(800);
+    }
+    if let Some(pos) = candidate.find(query) {
+        let at_boundary = pos == 0 || candidate[..pos].ends_with(['/', '-', '_', '.']);
+        return Some(if at_boundary { 600 } else { 400 });
+    }
+
+    // Subsequence match; fewer gaps score higher
+    let mut chars = candidate.chars();
+    let mut gaps = 0u32;
+    for q in query.chars() {
+        loop {
+            match chars.next() {
+                Some(c) if c == q => break,
+                Some(_) => gaps += 1,
+                None => return None,
+            }
+        }
+    }
+    Some(200u32.saturating_sub(gaps))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_match_score_ranking() {
+        let exact = match_score("api", "api").unwrap();
+        let suffix = match_score("feature/api", "api").unwrap();
+        let boundary = match_score("feature/api-v2", "api").unwrap();
+        let substring = match_score("rapid", "api").unwrap();
+        let subsequence = match_score("auth-pipeline", "api").unwrap();
+
+        assert!(exact > suffix);
+        assert!(suffix > boundary);
+        assert!(boundary > substring);
+        assert!(substring > subsequence);
+        assert_eq!(match_score("main", "api"), None);
+    }
+}
@@ -1,49 +1,232 @@
+mod age;
 mod app;
+mod archive;
+mod cache;
 mod config;
+mod editor;
+mod filter;
 mod git;
+mod jump;
+mod report;
+mod shell;
+mod sort;
+mod state;
 mod status;
 mod ui;
+mod watch;
 
 use anyhow::Result;
 use app::App;
 use std::env;
 use std::io::{self, Write};
+use std::path::{Path, PathBuf};
 
 fn main() -> Result<()> {
-    let args: Vec<String> = env::args().collect();
+    let mut args: Vec<String> = env::args().collect();
+
+    // `-C <path>`: run as if started in <path>, like git
+    let start_dir = match args.iter().position(|a| a == "-C") {
+        Some(i) if i + 1 < args.len() => {
+            let dir = PathBuf::from(args.remove(i + 1));
+            args.remove(i);
+            dir
+        }
+        Some(_) => anyhow::bail!("-C needs a path"),
+        None => env::current_dir()?,
+    };
 
     // Handle -m flag: go directly to main worktree
     if args.iter().any(|a| a == "-m" || a == "--main") {
-        let repo_path = std::env::current_dir()?;
-        let worktrees = git::list_worktrees(&repo_path)?;
+        let repo_path = git::main_repo(&start_dir)?;
+        let worktrees = git::list_worktrees(&repo_path, notes_storage())?;
         if let Some(main_wt) = worktrees.into_iter().find(|w| w.is_main) {
-            writeln!(io::stderr(), "{}", main_wt.path.display())?;
+            let mut state = state::State::load();
+            state.record_enter(&main_wt.path);
+            let _ = state.save();
+            write_exit_path(&main_wt.path)?;
         }
         return Ok(());
     }
 
+    match args.get(1).map(String::as_str) {
+        // Print the best fuzzy/frecency match without the TUI
+        Some("jump") => {
+            let query = args[2..].join(" ");
+            let repo_path = git::main_repo(&start_dir)?;
+            let mut worktrees = git::list_worktrees(&repo_path, notes_storage())?;
+            let mut state = state::State::load();
+            state.apply_history(&mut worktrees);
+
+            let Some(wt) = jump::best_match(&worktrees, &query) else {
+                anyhow::bail!("No worktree matches '{}'", query);
+            };
+            state.record_enter(&wt.path);
+            let _ = state.save();
+            return write_exit_path(&wt.path);
+        }
+        // Print worktrees with their parsed status files, for scripts
+        Some("list") => {
+            let repo_path = git::main_repo(&start_dir)?;
+            let mut worktrees = git::list_worktrees(&repo_path, notes_storage())?;
+            state::State::load().apply_history(&mut worktrees);
+
+            if args.iter().any(|a| a == "--json") {
+                println!("{}", serde_json::to_string_pretty(&worktrees)?);
+            } else {
+                for wt in &worktrees {
+                    let (checked, total) = wt.status.progress;
+                    println!(
+                        "{:<30} {:<8} {:>5}  {}",
+                        wt.branch.as_deref().unwrap_or("(detached)"),
+                        wt.status.meta.state.as_deref().unwrap_or("-"),
+                        if total > 0 { format!("{}/{}", checked, total) } else { "-".to_string() },
+                        wt.status.purpose.as_deref().unwrap_or(""),
+                    );
+                }
+            }
+            return Ok(());
+        }
+        // Standup report of all worktrees
+        Some("report") => {
+            let config = config::Config::load().unwrap_or_default();
+            let format = match flag_value(&args, "--format") {
+                Some(name) => report::ReportFormat::parse(name)?,
+                None => report::ReportFormat::Markdown,
+            };
+            let repo_path = git::main_repo(&start_dir)?;
+            let worktrees = git::list_worktrees(&repo_path, config.notes_storage)?;
+            let output = report::render(&worktrees, format, &config.report);
+
+            match flag_value(&args, "-o").or(flag_value(&args, "--output")) {
+                Some(file) => std::fs::write(file, output)?,
+                None => print!("{}", output),
+            }
+            return Ok(());
+        }
+        // Search the notes of deleted worktrees
+        Some("history") => {
+            let show_notes = args.iter().any(|a| a == "-v" || a == "--notes");
+            let query: Vec<&str> = args[2..]
+                .iter()
+                .map(String::as_str)
+                .filter(|a| !a.starts_with('-'))
+                .collect();
+            let archive = archive::Archive::load(&git::main_repo(&start_dir)?)?;
+
+            for entry in archive.search(&query.join(" ")) {
+                let (checked, total) = entry.progress;
+                println!(
+                    "{}  {:<30} {}  {:>5}  {}",
+                    age::format_date(entry.archived_at),
+                    entry.branch,
+                    entry.commit,
+                    if total > 0 { format!("{}/{}", checked, total) } else { "-".to_string() },
+                    entry.purpose.as_deref().unwrap_or(""),
+                );
+                if show_notes {
+                    if let Some(notes) = &entry.notes {
+                        for line in notes.lines() {
+                            println!("    {}", line);
+                        }
+                    }
+                    println!();
+                }
+            }
+            return Ok(());
+        }
+        // Move status files out of the worktrees after switching notes_storage
+        Some("migrate-notes") => {
+            let notes = notes_storage();
+            if notes == config::NotesStorage::Worktree {
+                anyhow::bail!("notes_storage is \"worktree\", there is nothing to move");
+            }
+            let repo_path = git::main_repo(&start_dir)?;
+            for wt in git::list_worktrees(&repo_path, notes)? {
+                let status_path = status::status_path(notes, &wt.path, wt.branch.as_deref());
+                match status::migrate_status_file(&wt.path, &status_path) {
+                    Ok(true) => println!(
+                        "Moved {} to {}",
+                        wt.path.join(status::STATUS_FILE).display(),
+                        status_path.display()
+                    ),
+                    Ok(false) => {}
+                    Err(e) => eprintln!("Failed to move the status file of {}: {}", wt.path.display(), e),
+                }
+            }
+            return Ok(());
+        }
+        // Set up a bare repository with worktrees and cd into the home one
+        Some("clone") => {
+            let rest: Vec<&str> = args[2..]
+                .iter()
+                .map(String::as_str)
+                .filter(|a| *a != "--bare")
+                .collect();
+            if rest.len() == args.len() - 2 {
+                anyhow::bail!("Only `wtm clone --bare <url-or-path> [dir]` is supported, use git clone otherwise");
+            }
+            let Some(source) = rest.first() else {
+                anyhow::bail!("Usage: wtm clone --bare <url-or-path> [dir]");
+            };
+            let home = git::clone_bare(source, rest.get(1).map(Path::new), &start_dir)?;
+            println!("Cloned into {}", home.display());
+            return write_exit_path(&home);
+        }
+        // Print the shell wrapper and completions
+        Some("shell-init") => {
+            let shell = args.get(2).map(String::as_str).unwrap_or("");
+            print!("{}", shell::init_script(shell)?);
+            return Ok(());
+        }
+        _ => {}
+    }
+
     // Setup panic handler to restore terminal
     let original_hook = std::panic::take_hook();
     std::panic::set_hook(Box::new(move |panic| {
-        let _ = ratatui::restore();
+        ui::restore_terminal();
         original_hook(panic);
     }));
 
+    // Before touching the terminal, so errors like "not a git repository" print normally
+    let dashboard = args.get(1).is_some_and(|a| a == "dashboard");
+    let mut app = App::new(&start_dir, dashboard)?;
+
     // Initialize terminal
-    let terminal = ratatui::init();
+    let terminal = ui::init_terminal();
 
     // Run app
-    let mut app = App::new()?;
     let result = app.run(terminal);
 
     // Cleanup
-    ratatui::restore();
+    ui::restore_terminal();
 
-    // If user selected a worktree to exit into, print path to stderr
-    // (stdout is used by TUI, stderr is captured by shell wrapper)
     if let Some(path) = &app.exit_path {
-        writeln!(io::stderr(), "{}", path.display())?;
+        write_exit_path(path)?;
     }
 
     result
 }
+
+/// Value following a flag, e.g. `--format html`
+fn flag_value<'a>(args: &'a [String], flag: &str) -> Option<&'a str> {
+    let i = args.iter().position(|a| a == flag)?;
+    args.get(i + 1).map(String::as_str)
+}
+
+/// Configured status file storage, for subcommands that run without the TUI
+fn notes_storage() -> config::NotesStorage {
+    config::Config::load().unwrap_or_default().notes_storage
+}
+
+/// Hand the selected worktree path to the shell wrapper.
+/// Wrappers from `wtm shell-init` pass a temp file in `WTM_CD_FILE`, which keeps
+/// warnings on stderr from being mistaken for a path. Older hand-written
+/// wrappers capture stderr, so fall back to that when the variable is unset.
+fn write_exit_path(path: &Path) -> Result<()> {
+    match env::var_os(shell::CD_FILE_VAR) {
+        Some(file) => std::fs::write(file, path.to_string_lossy().as_bytes())?,
+        None => writeln!(io::stderr(), "{}", path.display())?,
+    }
+    Ok(())
+}
@@ -0,0 +1,283 @@
+use std::collections::HashMap;
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::git::Worktree;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReportFormat {
+    Markdown,
+    Html,
+    Text,
+}
+
+impl ReportFormat {
+    pub fn parse(name: &str) -> Result<Self> {
+        match name {
+            "md" | "markdown" => Ok(Self::Markdown),
+            "html" => Ok(Self::Html),
+            "text" | "txt" => Ok(Self::Text),
+            other => Err(anyhow!("Unknown report format '{}' (expected markdown, html or text)", other)),
+        }
+    }
+
+    /// Key of this format's `[report.<name>]` table in the config
+    fn name(self) -> &'static str {
+        match self {
+            Self::Markdown => "markdown",
+            Self::Html => "html",
+            Self::Text => "text",
+        }
+    }
+}
+
+/// Report layout for one format. Each part is optional and falls back to the
+/// built-in one. `item` is repeated per worktree.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReportTemplate {
+    pub header: Option<String>,
+    pub item: Option<String>,
+    pub footer: Option<String>,
+}
+
+const MARKDOWN: (&str, &str, &str) = (
+    "## Worktrees ({date})\n\n",
+    "- **{branch}** {progress}{dirty}{ahead_behind}\n  {purpose}\n{open_tasks}",
+    "",
+);
+
+const HTML: (&str, &str, &str) = (
+    "<h2>Worktrees ({date})</h2>\n<ul>\n",
+    "<li><b>{branch}</b> {progress}{dirty}{ahead_behind}<br>{purpose}\n{open_tasks}</li>\n",
+    "</ul>\n",
+);
+
+const TEXT: (&str, &str, &str) = (
+    "Worktrees ({date})\n\n",
+    "{branch} {progress}{dirty}{ahead_behind}\n  {purpose}\n{open_tasks}\n",
+    "",
+);
+
+/// Render worktrees with status notes (or any non-main worktree) into a report
+pub fn render(
+    worktrees: &[Worktree],
+    format: ReportFormat,
+    templates: &HashMap<String, ReportTemplate>,
+) -> String {
+    let (header, item, footer) = match format {
+        ReportFormat::Markdown => MARKDOWN,
+        ReportFormat::Html => HTML,
+        ReportFormat::Text => TEXT,
+    };
+    let custom = templates.get(format.name()).cloned().unwrap_or_default();
+    let header = custom.header.as_deref().unwrap_or(header);
+    let item = custom.item.as_deref().unwrap_or(item);
+    let footer = custom.footer.as_deref().unwrap_or(footer);
+
+    let date = chrono::Local::now().format("%Y-%m-%d").to_string();
+    let mut report = header.replace("{date}", &date);
+    for wt in worktrees.iter().filter(|w| !w.is_bare && (!w.is_main || w.status.has_file)) {
+        report.push_str(&render_item(item, wt, format));
+    }
+    report.push_str(&footer.replace("{date}", &date));
+    report
+}
+
+fn render_item(template: &str, wt: &Worktree, format: ReportFormat) -> String {
+    let escape = |s: &str| match format {
+        ReportFormat::Html => html_escape(s),
+        _ => s.to_string(),
+    };
+
+    let (checked, total) = wt.status.progress;
+    let progress = if total > 0 {
+        format!("[{}/{}]", checked, total)
+    } else {
+        String::new()
+    };
+    let dirty = if wt.has_changes { " (uncommitted changes)" } else { "" };
+    let ahead_behind = match (wt.ahead, wt.behind) {
+        (0, 0) => String::new(),
+        (ahead, behind) => format!(" ↑{} ↓{}", ahead, behind),
+    };
+
+    let item = fill_placeholders(template, |name| {
+        Some(match name {
+            "branch" => escape(wt.branch.as_deref().unwrap_or("(detached)")),
+            "path" => escape(&wt.path.to_string_lossy()),
+            "purpose" => escape(wt.status.purpose.as_deref().unwrap_or("")),
+            "state" => escape(wt.status.meta.state.as_deref().unwrap_or("")),
+            "progress" => progress.clone(),
+            "dirty" => dirty.to_string(),
+            "ahead_behind" => ahead_behind.clone(),
+            "ahead" => wt.ahead.to_string(),
+            "behind" => wt.behind.to_string(),
+            "open_tasks" => open_tasks(wt, format),
+            _ => return None,
+        })
+    });
+
+    // Drop lines left holding only indentation by empty placeholders, and the
+    // trailing space they leave elsewhere
+    item.split_inclusive('\n')
+        .filter(|line| !line.trim().is_empty() || line.trim_end_matches('\n').is_empty())
+        .map(|line| match line.strip_suffix('\n') {
+            Some(text) => format!("{}\n", text.trim_end()),
+            None => line.trim_end().to_string(),
+        })
+        .collect()
+}
+
+/// Replace `{name}` placeholders in one pass, so values that contain
+/// placeholders themselves (a purpose mentioning `{state}`) are left alone.
+/// Unknown names are kept as written.
+fn fill_placeholders(template: &str, value: impl Fn(&str) -> Option<String>) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        match after.find('}').and_then(|end| Some((end, value(&after[..end])?))) {
+            Some((end, text)) => {
+                out.push_str(&text);
+                rest = &after[end + 1..];
+            }
+            None => {
+                out.push('{');
+                rest = after;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Unchecked tasks as a nested list in the report's format
+fn open_tasks(wt: &Worktree, format: ReportFormat) -> String {
+    let tasks: Vec<_> = wt
+        .status
+        .sections
+        .iter()
+        .flat_map(|s| &s.tasks)
+        .filter(|t| !t.checked)
+        .collect();
+    if tasks.is_empty() {
+        return String::new();
+    }
+
+    match format {
+        ReportFormat::Markdown => tasks
+            .iter()
+            .map(|t| format!("{}  - [ ] {}\n", "  ".repeat(t.depth), t.text))
+            .collect(),
+        ReportFormat::Text => tasks
+            .iter()
+            .map(|t| format!("{}    - {}\n", "  ".repeat(t.depth), t.text))
+            .collect(),
+        ReportFormat::Html => {
+            let items: String = tasks
+                .iter()
+                .map(|t| format!("<li>{}</li>", html_escape(&t.text)))
+                .collect();
+            format!("<ul>{}</ul>", items)
+        }
+    }
+}
+
+/// Put text on the system clipboard with an OSC 52 escape, which terminals
+/// (and tmux with `set-clipboard on`) pass through even over ssh
+pub fn copy_to_clipboard(text: &str) -> Result<()> {
+    use std::io::Write;
+    let mut stdout = std::io::stdout();
+    write!(stdout, "\x1b]52;c;{}\x07", base64(text.as_bytes()))?;
+    stdout.flush()?;
+    Ok(())
+}
+
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk.iter().enumerate().fold(0u32, |n, (i, b)| n | (*b as u32) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[(n >> (18 - 6 * i) & 63) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+fn html_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::WorktreeStatus;
+    use std::path::PathBuf;
+
+    fn worktree(branch: &str, notes: &str) -> Worktree {
+        let mut status: WorktreeStatus = crate::status::parse_status_file(notes);
+        status.has_file = true;
+        Worktree {
+            path: PathBuf::from("/tmp/wt"),
+            branch: Some(branch.to_string()),
+            has_changes: true,
+            changes: crate::git::StatusCounts {
+                modified: 1,
+                ..Default::default()
+            },
+            status,
+            ahead: 2,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_markdown_report() {
+        let wt = worktree("feature/login", "## Purpose\nOAuth <login>\n\n## Status\n- [x] Done\n- [ ] Tests\n");
+        let report = render(&[wt], ReportFormat::Markdown, &HashMap::new());
+        assert!(report.contains(
+            "- **feature/login** [1/2] (uncommitted changes) ↑2 ↓0\n  OAuth <login>\n  - [ ] Tests\n"
+        ));
+    }
+
+    #[test]
+    fn test_placeholders_in_values() {
+        let wt = worktree("feature/{state}", "## Purpose\nDocument {open_tasks} and {{path}\n");
+        let report = render(&[wt], ReportFormat::Text, &HashMap::new());
+        assert!(report.contains("feature/{state}"));
+        assert!(report.contains("Document {open_tasks} and {{path}"));
+    }
+
+    #[test]
+    fn test_base64() {
+        assert_eq!(base64(b"Ma"), "TWE=");
+        assert_eq!(base64(b"Man"), "TWFu");
+        assert_eq!(base64(b"M"), "TQ==");
+    }
+
+    #[test]
+    fn test_html_escapes_and_custom_template() {
+        let wt = worktree("feature/login", "## Purpose\nOAuth <login>\n");
+        let mut templates = HashMap::new();
+        templates.insert(
+            "html".to_string(),
+            ReportTemplate {
+                item: Some("<p>{branch}: {purpose}</p>".to_string()),
+                ..Default::default()
+            },
+        );
+        let report = render(&[wt], ReportFormat::Html, &templates);
+        assert!(report.contains("<p>feature/login: OAuth &lt;login&gt;</p>"));
+        assert!(report.ends_with("</ul>\n"));
+    }
+}
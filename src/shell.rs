@@ -0,0 +1,161 @@
+use anyhow::{anyhow, Result};
+
+/// Environment variable naming the file wtm writes the selected path to
+pub const CD_FILE_VAR: &str = "WTM_CD_FILE";
+
+/// Subcommands offered by tab completion: (name, description)
+const SUBCOMMANDS: &[(&str, &str)] = &[
+    ("jump", "Jump to the best matching worktree"),
+    ("list", "Print worktrees and their status (--json)"),
+    ("report", "Print a status report (--format markdown|html|text, -o file)"),
+    ("history", "Search notes of deleted worktrees (--notes)"),
+    ("dashboard", "Browse the worktrees of all configured repos"),
+    ("migrate-notes", "Move status files out of worktrees to notes_storage"),
+    ("clone", "Clone as a bare repository with worktrees (--bare)"),
+    ("shell-init", "Print shell integration"),
+];
+
+const SHELLS: &str = "bash zsh fish nushell";
+
+const BASH_INIT: &str = r#"# wtm shell integration for bash
+# Add to ~/.bashrc:  eval "$(wtm shell-init bash)"
+
+wtm() {
+    local tmp dir code
+    tmp="$(mktemp "${TMPDIR:-/tmp}/wtm.XXXXXX")" || return
+    WTM_CD_FILE="$tmp" command wtm "$@"
+    code=$?
+    dir="$(cat "$tmp")"
+    rm -f "$tmp"
+    if [[ -n "$dir" && -d "$dir" ]]; then
+        cd "$dir" || return
+    fi
+    return $code
+}
+
+_wtm_complete() {
+    local cur="${COMP_WORDS[COMP_CWORD]}"
+    if [[ $COMP_CWORD -eq 1 ]]; then
+        COMPREPLY=($(compgen -W "{subcommands} -m --main -C" -- "$cur"))
+    elif [[ ${COMP_WORDS[1]} == jump ]]; then
+        COMPREPLY=($(compgen -W "$(git worktree list --porcelain 2>/dev/null | sed -n 's|^branch refs/heads/||p')" -- "$cur"))
+    elif [[ ${COMP_WORDS[1]} == shell-init ]]; then
+        COMPREPLY=($(compgen -W "{shells}" -- "$cur"))
+    fi
+}
+complete -F _wtm_complete wtm
+"#;
+
+const ZSH_INIT: &str = r#"# wtm shell integration for zsh
+# Add to ~/.zshrc:  eval "$(wtm shell-init zsh)"
+
+wtm() {
+    local tmp dir code
+    tmp="$(mktemp "${TMPDIR:-/tmp}/wtm.XXXXXX")" || return
+    WTM_CD_FILE="$tmp" command wtm "$@"
+    code=$?
+    dir="$(cat "$tmp")"
+    rm -f "$tmp"
+    if [[ -n "$dir" && -d "$dir" ]]; then
+        cd "$dir" || return
+    fi
+    return $code
+}
+
+_wtm() {
+    if (( CURRENT == 2 )); then
+        compadd -- {subcommands} -m --main -C
+    elif [[ ${words[2]} == jump ]]; then
+        compadd -- ${(f)"$(git worktree list --porcelain 2>/dev/null | sed -n 's|^branch refs/heads/||p')"}
+    elif [[ ${words[2]} == shell-init ]]; then
+        compadd -- {shells}
+    fi
+}
+(( $+functions[compdef] )) && compdef _wtm wtm
+"#;
+
+const FISH_INIT: &str = r#"# wtm shell integration for fish
+# Add to ~/.config/fish/config.fish:  wtm shell-init fish | source
+
+function wtm
+    set -l tmp (mktemp)
+    WTM_CD_FILE=$tmp command wtm $argv
+    set -l code $status
+    set -l dir (cat $tmp)
+    rm -f $tmp
+    if test -n "$dir" -a -d "$dir"
+        cd $dir
+    end
+    return $code
+end
+
+complete -c wtm -f
+complete -c wtm -s m -l main -d 'Go to the main worktree'
+complete -c wtm -s C -r -F -d 'Run in another repository'
+{subcommand_completions}
+complete -c wtm -n '__fish_seen_subcommand_from jump' -a '(git worktree list --porcelain 2>/dev/null | string replace -rf "^branch refs/heads/" "")'
+complete -c wtm -n '__fish_seen_subcommand_from shell-init' -a '{shells}'
+"#;
+
+const NUSHELL_INIT: &str = r#"# wtm shell integration for nushell
+# Save and source it from config.nu:
+#   wtm shell-init nushell | save -f ~/.config/nushell/wtm.nu
+#   source ~/.config/nushell/wtm.nu
+
+def "nu-complete wtm" [context: string] {
+    let words = ($context | str trim | split row -r '\s+')
+    let subcommand = if ($words | length) > 1 { $words | get 1 } else { "" }
+    if ($words | length) <= 2 and not ($context | str ends-with " ") {
+        [{subcommands_list} "-m" "--main" "-C"]
+    } else if $subcommand == "jump" {
+        git worktree list --porcelain | lines | where ($it | str starts-with "branch refs/heads/") | each { |l| $l | str replace "branch refs/heads/" "" }
+    } else if $subcommand == "shell-init" {
+        [{shells_list}]
+    } else {
+        []
+    }
+}
+
+def --env --wrapped wtm [...args: string@"nu-complete wtm"] {
+    let tmp = (mktemp -t wtm.XXXXXX)
+    with-env { WTM_CD_FILE: $tmp } { ^wtm ...$args }
+    let dir = (open --raw $tmp | str trim)
+    rm -f $tmp
+    if ($dir | is-not-empty) and ($dir | path exists) {
+        cd $dir
+    }
+}
+"#;
+
+/// Shell wrapper (cd on exit via a temp file) plus tab completions
+pub fn init_script(shell: &str) -> Result<String> {
+    let subcommands: Vec<&str> = SUBCOMMANDS.iter().map(|(name, _)| *name).collect();
+
+    let script = match shell {
+        "bash" => BASH_INIT.replace("{subcommands}", &subcommands.join(" ")),
+        "zsh" => ZSH_INIT.replace("{subcommands}", &subcommands.join(" ")),
+        "fish" => {
+            let completions: Vec<String> = SUBCOMMANDS
+                .iter()
+                .map(|(name, description)| {
+                    format!(
+                        "complete -c wtm -n __fish_use_subcommand -a {} -d '{}'",
+                        name, description
+                    )
+                })
+                .collect();
+            FISH_INIT.replace("{subcommand_completions}", &completions.join("\n"))
+        }
+        "nushell" | "nu" => {
+            let quoted: Vec<String> = subcommands.iter().map(|s| format!("\"{}\"", s)).collect();
+            let shells: Vec<String> = SHELLS.split(' ').map(|s| format!("\"{}\"", s)).collect();
+            NUSHELL_INIT
+                .replace("{subcommands_list}", &quoted.join(" "))
+                .replace("{shells_list}", &shells.join(" "))
+        }
+        "" => return Err(anyhow!("Usage: wtm shell-init <{}>", SHELLS.replace(' ', "|"))),
+        other => return Err(anyhow!("Unsupported shell '{}' (expected one of: {})", other, SHELLS)),
+    };
+
+    Ok(script.replace("{shells}", SHELLS))
+}
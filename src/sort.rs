@@ -0,0 +1,117 @@
+use std::cmp::Reverse;
+
+use serde::{Deserialize, Serialize};
+
+use crate::git::Worktree;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortMode {
+    #[default]
+    Default, // `git worktree list` order
+    Name,
+    LastCommit,
+    LastModified,
+    Ahead,
+    Progress,
+    Frecency,
+}
+
+impl SortMode {
+    pub fn next(self) -> Self {
+        match self {
+            Self::Default => Self::Name,
+            Self::Name => Self::LastCommit,
+            Self::LastCommit => Self::LastModified,
+            Self::LastModified => Self::Ahead,
+            Self::Ahead => Self::Progress,
+            Self::Progress => Self::Frecency,
+            Self::Frecency => Self::Default,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Default => "default",
+            Self::Name => "name",
+            Self::LastCommit => "last commit",
+            Self::LastModified => "last modified",
+            Self::Ahead => "ahead",
+            Self::Progress => "progress",
+            Self::Frecency => "frecency",
+        }
+    }
+}
+
+/// Sort worktree indices in place. The main worktree stays first except in
+/// frecency mode, where it competes like any other worktree; time and count
+/// based modes put the largest values first. A bare repository always goes last.
+pub fn sort_indices(indices: &mut [usize], worktrees: &[Worktree], mode: SortMode) {
+    let key = |i: &usize| (worktrees[*i].is_bare, !worktrees[*i].is_main);
+
+    match mode {
+        SortMode::Default => indices.sort_by_key(key),
+        SortMode::Name => indices.sort_by_key(|i| (key(i), branch_name(&worktrees[*i]))),
+        SortMode::LastCommit => {
+            indices.sort_by_key(|i| (key(i), Reverse(worktrees[*i].last_commit_time)))
+        }
+        SortMode::LastModified => {
+            indices.sort_by_key(|i| (key(i), Reverse(worktrees[*i].last_modified)))
+        }
+        SortMode::Ahead => indices.sort_by_key(|i| (key(i), Reverse(worktrees[*i].ahead))),
+        SortMode::Progress => {
+            indices.sort_by_key(|i| (key(i), Reverse(progress_percent(&worktrees[*i]))))
+        }
+        SortMode::Frecency => indices.sort_by(|a, b| {
+            let (a, b) = (&worktrees[*a], &worktrees[*b]);
+            a.is_bare.cmp(&b.is_bare).then(b.frecency.total_cmp(&a.frecency))
+        }),
+    }
+}
+
+fn branch_name(wt: &Worktree) -> String {
+    wt.branch.as_deref().unwrap_or("").to_lowercase()
+}
+
+/// Progress as a percentage, `None` for worktrees without tasks (sorted last)
+fn progress_percent(wt: &Worktree) -> Option<u32> {
+    let (checked, total) = wt.status.progress;
+    (total > 0).then(|| checked * 100 / total)
+}
+
+/// Group name for a branch: everything before the first `/`
+/// (`feature/login` -> `feature`, `alice/spike` -> `alice`).
+/// Main and branches without a prefix are not grouped.
+pub fn group_name(wt: &Worktree) -> Option<&str> {
+    if wt.is_main {
+        return None;
+    }
+    let branch = wt.branch.as_deref()?;
+    branch.split_once('/').map(|(prefix, _)| prefix)
+}
+
+/// Directory name of the worktree's repository, which groups the dashboard.
+/// Bare repositories go by their directory without `.git`, or by the
+/// directory holding them when hidden (`project/.bare`).
+pub fn repo_name(wt: &Worktree) -> &str {
+    match wt.repo.file_name().and_then(|n| n.to_str()) {
+        Some(n) if n.starts_with('.') => wt
+            .repo
+            .parent()
+            .and_then(|p| p.file_name())
+            .and_then(|n| n.to_str())
+            .unwrap_or(n),
+        Some(n) => n.strip_suffix(".git").unwrap_or(n),
+        None => "repo",
+    }
+}
+
+/// Group of a worktree in the list: its repository in the dashboard, else
+/// its branch prefix
+pub fn group_of(wt: &Worktree, by_repo: bool) -> Option<&str> {
+    if by_repo {
+        Some(repo_name(wt))
+    } else {
+        group_name(wt)
+    }
+}
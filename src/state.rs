@@ -0,0 +1,146 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::git::Worktree;
+use crate::sort::SortMode;
+
+/// UI state remembered between runs (unlike `Config`, never edited by hand)
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct State {
+    #[serde(default)]
+    pub sort: SortMode,
+    #[serde(default)]
+    pub group_by_prefix: bool,
+    #[serde(default)]
+    pub collapsed_groups: Vec<String>,
+    /// Per-worktree usage, keyed by worktree path
+    #[serde(default)]
+    pub worktrees: HashMap<String, WorktreeHistory>,
+}
+
+/// Number of recent visits kept per worktree for frecency scoring
+const MAX_VISITS: usize = 20;
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct WorktreeHistory {
+    pub last_entered: Option<i64>, // unix seconds
+    #[serde(default)]
+    pub visits: Vec<i64>, // most recent last, capped at MAX_VISITS
+}
+
+impl WorktreeHistory {
+    /// Frequency weighted by recency: recent visits count for more,
+    /// so a worktree used a lot last month ranks below today's work
+    pub fn frecency(&self, now: i64) -> f64 {
+        self.visits
+            .iter()
+            .map(|&t| {
+                let age = now - t;
+                if age < 3600 {
+                    8.0
+                } else if age < 86400 {
+                    4.0
+                } else if age < 7 * 86400 {
+                    2.0
+                } else if age < 30 * 86400 {
+                    1.0
+                } else {
+                    0.25
+                }
+            })
+            .sum()
+    }
+}
+
+impl State {
+    /// Load state, falling back to defaults if the file is missing or unreadable
+    pub fn load() -> Self {
+        Self::state_path()
+            .ok()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|content| toml::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let state_path = Self::state_path()?;
+
+        if let Some(parent) = state_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        fs::write(&state_path, toml::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    pub fn history(&self, path: &Path) -> Option<&WorktreeHistory> {
+        self.worktrees.get(path.to_string_lossy().as_ref())
+    }
+
+    /// Record that the user entered a worktree through wtm
+    pub fn record_enter(&mut self, path: &Path) {
+        let history = self
+            .worktrees
+            .entry(path.to_string_lossy().into_owned())
+            .or_default();
+        let now = crate::age::now();
+        history.last_entered = Some(now);
+        history.visits.push(now);
+        if history.visits.len() > MAX_VISITS {
+            history.visits.remove(0);
+        }
+    }
+
+    /// Fill in the usage fields of freshly listed worktrees
+    pub fn apply_history(&self, worktrees: &mut [Worktree]) {
+        let now = crate::age::now();
+        for wt in worktrees {
+            if let Some(history) = self.history(&wt.path) {
+                wt.last_entered = history.last_entered;
+                wt.frecency = history.frecency(now);
+            }
+        }
+    }
+
+    fn state_path() -> Result<PathBuf> {
+        Ok(data_dir().join("state.toml"))
+    }
+}
+
+/// wtm's data directory: `$XDG_DATA_HOME/wtm` or `~/.local/share/wtm`,
+/// same reasoning as the config path
+pub fn data_dir() -> PathBuf {
+    std::env::var("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| {
+            dirs::home_dir()
+                .unwrap_or_else(|| PathBuf::from("."))
+                .join(".local")
+                .join("share")
+        })
+        .join("wtm")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frecency_prefers_recent_visits() {
+        let now = 1_000_000_000;
+        let recent = WorktreeHistory {
+            visits: vec![now - 60, now - 2 * 3600],
+            ..Default::default()
+        };
+        let old = WorktreeHistory {
+            visits: vec![now - 40 * 86400; 10],
+            ..Default::default()
+        };
+        assert!(recent.frecency(now) > old.frecency(now));
+        assert_eq!(WorktreeHistory::default().frecency(now), 0.0);
+    }
+}
@@ -1,3 +1,13 @@
+mod model;
+mod section;
+mod storage;
+mod tasks;
 mod template;
 
-pub use template::{generate_status_file, parse_status_file};
+pub use model::{parse_status_file, Frontmatter, Link, Section};
+pub use section::{replace_section, section_body};
+pub use storage::{migrate_status_file, needs_migration, repo_key, status_path, STATUS_FILE};
+pub use tasks::{append_task, find_tasks, toggle_task, Task};
+pub use template::{
+    generate_status_file, list_templates, load_template, select_template, TemplateVars,
+};
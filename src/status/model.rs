@@ -0,0 +1,318 @@
+use serde::{Deserialize, Deserializer, Serialize};
+
+use super::{find_tasks, Task};
+use crate::git::WorktreeStatus;
+
+/// Metadata from a YAML (`---`) or TOML (`+++`) block at the top of the file
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Frontmatter {
+    #[serde(deserialize_with = "scalar", skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+    #[serde(deserialize_with = "scalar", skip_serializing_if = "Option::is_none")]
+    pub ticket: Option<String>,
+    #[serde(deserialize_with = "scalar", skip_serializing_if = "Option::is_none")]
+    pub priority: Option<String>,
+    #[serde(deserialize_with = "scalar", skip_serializing_if = "Option::is_none")]
+    pub due: Option<String>, // YYYY-MM-DD
+    #[serde(deserialize_with = "scalar", skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>, // wip, review, blocked, done, ...
+    #[serde(deserialize_with = "string_list", skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+}
+
+impl Frontmatter {
+    /// Due date has passed (and the work isn't done)
+    pub fn is_overdue(&self, today: chrono::NaiveDate) -> bool {
+        let done = self.state.as_deref() == Some("done");
+        !done
+            && self
+                .due
+                .as_deref()
+                .and_then(|d| chrono::NaiveDate::parse_from_str(d, "%Y-%m-%d").ok())
+                .is_some_and(|due| due < today)
+    }
+}
+
+/// A `#`..`######` heading and everything up to the next heading
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Section {
+    pub title: String,
+    pub level: u8,
+    pub line: usize, // 0-based line of the heading
+    pub body: String,
+    pub tasks: Vec<Task>,
+    pub progress: (u32, u32), // (checked, total) within this section
+}
+
+/// An entry under `## Related`: a markdown link, bare URL or plain reference
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Link {
+    pub text: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+pub fn parse_status_file(content: &str) -> WorktreeStatus {
+    let (meta, body_line) = parse_frontmatter(content);
+    let tasks = find_tasks(content);
+    let sections = parse_sections(content, body_line, &tasks);
+
+    let checked = tasks.iter().filter(|t| t.checked).count() as u32;
+
+    // Purpose: first non-empty, non-comment line of `## Purpose`
+    let purpose = find_section(&sections, "Purpose").and_then(|s| {
+        s.body
+            .lines()
+            .map(str::trim)
+            .find(|l| !l.is_empty() && !l.starts_with("<!--"))
+            .map(str::to_string)
+    });
+    let links = find_section(&sections, "Related").map_or_else(Vec::new, |s| parse_links(&s.body));
+
+    WorktreeStatus {
+        purpose,
+        progress: (checked, tasks.len() as u32),
+        has_file: false,
+        meta,
+        sections,
+        links,
+    }
+}
+
+fn find_section<'a>(sections: &'a [Section], title: &str) -> Option<&'a Section> {
+    sections.iter().find(|s| s.level == 2 && s.title == title)
+}
+
+/// Parse a leading frontmatter block. Returns the metadata and the first line
+/// after the block. Malformed frontmatter is ignored rather than failing the file.
+fn parse_frontmatter(content: &str) -> (Frontmatter, usize) {
+    let mut lines = content.split('\n');
+    let delimiter = match lines.next().map(str::trim_end) {
+        Some("---") => "---",
+        Some("+++") => "+++",
+        _ => return (Frontmatter::default(), 0),
+    };
+    let Some(end) = lines.position(|l| l.trim_end() == delimiter) else {
+        return (Frontmatter::default(), 0);
+    };
+
+    let block: Vec<&str> = content.split('\n').skip(1).take(end).collect();
+    let block = block.join("\n");
+    let meta = if delimiter == "---" {
+        serde_yaml::from_str(&block).ok()
+    } else {
+        // TOML dates are their own type; turn them into strings first
+        toml::from_str::<toml::Value>(&block)
+            .ok()
+            .map(dates_to_strings)
+            .and_then(|v| Frontmatter::deserialize(v).ok())
+    };
+
+    (meta.unwrap_or_default(), end + 2)
+}
+
+fn dates_to_strings(value: toml::Value) -> toml::Value {
+    match value {
+        toml::Value::Datetime(dt) => toml::Value::String(dt.to_string()),
+        toml::Value::Table(table) => toml::Value::Table(
+            table.into_iter().map(|(k, v)| (k, dates_to_strings(v))).collect(),
+        ),
+        other => other,
+    }
+}
+
+/// Split the file into sections by ATX heading, ignoring `#` lines in fenced code
+fn parse_sections(content: &str, first_line: usize, tasks: &[Task]) -> Vec<Section> {
+    let lines: Vec<&str> = content.split('\n').collect();
+    let mut headings = Vec::new();
+    let mut fence: Option<&str> = None;
+
+    for (i, line) in lines.iter().enumerate().skip(first_line) {
+        let trimmed = line.trim_start();
+        if let Some(open) = fence {
+            if trimmed.starts_with(open) {
+                fence = None;
+            }
+            continue;
+        }
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            fence = Some(&trimmed[..3]);
+            continue;
+        }
+
+        let level = line.chars().take_while(|c| *c == '#').count();
+        if (1..=6).contains(&level) && line[level..].starts_with(' ') {
+            headings.push((i, level as u8, line[level..].trim().to_string()));
+        }
+    }
+
+    headings
+        .iter()
+        .enumerate()
+        .map(|(n, (line, level, title))| {
+            let end = headings.get(n + 1).map_or(lines.len(), |h| h.0);
+            let tasks: Vec<Task> = tasks
+                .iter()
+                .filter(|t| t.line > *line && t.line < end)
+                .cloned()
+                .collect();
+            let checked = tasks.iter().filter(|t| t.checked).count() as u32;
+            Section {
+                title: title.clone(),
+                level: *level,
+                line: *line,
+                body: lines[line + 1..end].join("\n").trim_matches('\n').to_string(),
+                progress: (checked, tasks.len() as u32),
+                tasks,
+            }
+        })
+        .collect()
+}
+
+fn parse_links(body: &str) -> Vec<Link> {
+    let mut links = Vec::new();
+
+    for line in body.lines() {
+        let item = line.trim().trim_start_matches(['-', '*', '+']).trim();
+        if item.is_empty() || item.starts_with("<!--") {
+            continue;
+        }
+
+        // [text](url)
+        if let Some((text, rest)) = item.strip_prefix('[').and_then(|s| s.split_once("](")) {
+            if let Some((url, _)) = rest.split_once(')') {
+                links.push(Link {
+                    text: text.to_string(),
+                    url: Some(url.to_string()),
+                });
+                continue;
+            }
+        }
+
+        let url = item
+            .split_whitespace()
+            .find(|w| w.starts_with("http://") || w.starts_with("https://"));
+        links.push(Link {
+            text: item.to_string(),
+            url: url.map(str::to_string),
+        });
+    }
+
+    links
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Scalar {
+    String(String),
+    Integer(i64),
+    Float(f64),
+    Bool(bool),
+}
+
+impl Scalar {
+    fn into_string(self) -> String {
+        match self {
+            Scalar::String(s) => s,
+            Scalar::Integer(i) => i.to_string(),
+            Scalar::Float(f) => f.to_string(),
+            Scalar::Bool(b) => b.to_string(),
+        }
+    }
+}
+
+/// Accept `priority: 1` as well as `priority: high`
+fn scalar<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<String>, D::Error> {
+    Ok(Option::<Scalar>::deserialize(deserializer)?.map(Scalar::into_string))
+}
+
+/// Accept `tags: [a, b]` as well as `tags: a, b`
+fn string_list<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<String>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum List {
+        One(String),
+        Many(Vec<Scalar>),
+    }
+
+    Ok(match Option::<List>::deserialize(deserializer)? {
+        Some(List::One(s)) => s
+            .split(',')
+            .map(|t| t.trim().to_string())
+            .filter(|t| !t.is_empty())
+            .collect(),
+        Some(List::Many(items)) => items.into_iter().map(Scalar::into_string).collect(),
+        None => Vec::new(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_progress() {
+        let content = r#"# Worktree: test
+## Status
+- [x] Done
+- [ ] Not done
+- [X] Also done
+"#;
+        let status = parse_status_file(content);
+        assert_eq!(status.progress, (2, 3));
+    }
+
+    #[test]
+    fn test_parse_purpose() {
+        let content = r#"# Worktree: test
+## Purpose
+Implement OAuth2 authentication
+
+## Status
+"#;
+        let status = parse_status_file(content);
+        assert_eq!(status.purpose, Some("Implement OAuth2 authentication".to_string()));
+    }
+
+    #[test]
+    fn test_parse_frontmatter() {
+        let yaml = "---\nowner: alice\npriority: 1\ndue: 2026-11-01\nstate: review\ntags: [auth, backend]\n---\n# Worktree: x\n";
+        let status = parse_status_file(yaml);
+        assert_eq!(status.meta.owner.as_deref(), Some("alice"));
+        assert_eq!(status.meta.priority.as_deref(), Some("1"));
+        assert_eq!(status.meta.state.as_deref(), Some("review"));
+        assert_eq!(status.meta.tags, vec!["auth", "backend"]);
+        assert_eq!(status.sections[0].line, 7);
+
+        let toml = "+++\nticket = \"ABC-12\"\ndue = 2026-11-01\ntags = \"a, b\"\n+++\n";
+        let status = parse_status_file(toml);
+        assert_eq!(status.meta.ticket.as_deref(), Some("ABC-12"));
+        assert_eq!(status.meta.due.as_deref(), Some("2026-11-01"));
+        assert_eq!(status.meta.tags, vec!["a", "b"]);
+
+        let today = chrono::NaiveDate::from_ymd_opt(2026, 11, 2).unwrap();
+        assert!(status.meta.is_overdue(today));
+
+        // Broken frontmatter doesn't lose the rest of the file
+        let status = parse_status_file("---\nowner: [\n---\n## Purpose\nStill here\n");
+        assert_eq!(status.meta, Frontmatter::default());
+        assert_eq!(status.purpose.as_deref(), Some("Still here"));
+    }
+
+    #[test]
+    fn test_parse_sections_and_links() {
+        let content = "## Status\n- [x] A\n  - [ ] A.1\n\n```sh\n# not a heading\n```\n## Follow-ups\n- [ ] B\n\n## Related\n<!-- Issue #, PR # -->\n- [PR 42](https://example.com/pr/42)\n- Issue #7\n- see https://example.com/x\n";
+        let status = parse_status_file(content);
+        let titles: Vec<&str> = status.sections.iter().map(|s| s.title.as_str()).collect();
+        assert_eq!(titles, vec!["Status", "Follow-ups", "Related"]);
+        assert_eq!(status.sections[0].progress, (1, 2));
+        assert_eq!(status.sections[0].tasks[1].depth, 1);
+        assert_eq!(status.sections[1].progress, (0, 1));
+
+        assert_eq!(status.links.len(), 3);
+        assert_eq!(status.links[0].url.as_deref(), Some("https://example.com/pr/42"));
+        assert_eq!(status.links[1].url, None);
+        assert_eq!(status.links[2].url.as_deref(), Some("https://example.com/x"));
+    }
+}
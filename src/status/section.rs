@@ -0,0 +1,79 @@
+/// Line range of a `## <name>` section's body: (heading line, end line exclusive)
+fn section_range(lines: &[&str], name: &str) -> Option<(usize, usize)> {
+    let heading = lines
+        .iter()
+        .position(|l| l.strip_prefix("## ").is_some_and(|h| h.trim() == name))?;
+    let end = lines[heading + 1..]
+        .iter()
+        .position(|l| l.starts_with("## "))
+        .map_or(lines.len(), |i| heading + 1 + i);
+    Some((heading, end))
+}
+
+/// Body of a `## <name>` section without surrounding blank lines
+pub fn section_body(content: &str, name: &str) -> Option<String> {
+    let lines: Vec<&str> = content.split('\n').collect();
+    let (heading, end) = section_range(&lines, name)?;
+    Some(lines[heading + 1..end].join("\n").trim_matches('\n').to_string())
+}
+
+/// Replace the body of a `## <name>` section, leaving every other line as is.
+/// A missing section is appended at the end of the file.
+pub fn replace_section(content: &str, name: &str, body: &str) -> String {
+    let body = body.trim_matches('\n');
+    let lines: Vec<&str> = content.split('\n').collect();
+
+    let Some((heading, end)) = section_range(&lines, name) else {
+        let mut content = content.trim_end().to_string();
+        if !content.is_empty() {
+            content.push_str("\n\n");
+        }
+        content.push_str(&format!("## {}\n{}\n", name, body));
+        return content;
+    };
+
+    let mut result: Vec<&str> = lines[..=heading].to_vec();
+    if !body.is_empty() {
+        result.extend(body.split('\n'));
+    }
+    if end < lines.len() {
+        // Keep a blank line before the next heading
+        result.push("");
+    }
+    result.extend(&lines[end..]);
+
+    let mut content = result.join("\n");
+    if end == lines.len() {
+        content.push('\n');
+    }
+    content
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTENT: &str = "# Worktree: test\n\n## Purpose\n<!-- What -->\n\n\n## Status\n- [ ] Todo\n\n## Notes\nold\n";
+
+    #[test]
+    fn test_section_body() {
+        assert_eq!(section_body(CONTENT, "Purpose").unwrap(), "<!-- What -->");
+        assert_eq!(section_body(CONTENT, "Notes").unwrap(), "old");
+        assert_eq!(section_body(CONTENT, "Related"), None);
+    }
+
+    #[test]
+    fn test_replace_section() {
+        let updated = replace_section(CONTENT, "Purpose", "Fix login\nfor SSO users");
+        assert_eq!(
+            updated,
+            CONTENT.replace("<!-- What -->\n\n\n", "Fix login\nfor SSO users\n\n")
+        );
+
+        let updated = replace_section(CONTENT, "Notes", "new\n");
+        assert_eq!(updated, CONTENT.replace("old", "new"));
+
+        let updated = replace_section("# T\n", "Notes", "hi");
+        assert_eq!(updated, "# T\n\n## Notes\nhi\n");
+    }
+}
@@ -0,0 +1,83 @@
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::config::NotesStorage;
+
+/// Status file name in the worktree root (the default storage)
+pub const STATUS_FILE: &str = ".worktree-status.md";
+
+/// Where a worktree's status file lives for the configured storage.
+/// Falls back to the worktree root if git can't tell where its directories are.
+pub fn status_path(storage: NotesStorage, worktree_path: &Path, branch: Option<&str>) -> PathBuf {
+    let in_worktree = || worktree_path.join(STATUS_FILE);
+
+    match storage {
+        NotesStorage::Worktree => in_worktree(),
+        // .git/worktrees/<name>/wtm/status.md, or .git/wtm/status.md for main
+        NotesStorage::GitDir => crate::git::git_dirs(worktree_path)
+            .map(|(git_dir, _)| git_dir.join("wtm").join("status.md"))
+            .unwrap_or_else(in_worktree),
+        // <data dir>/notes/<repo>/<branch>.md, so notes follow the branch
+        NotesStorage::DataDir => {
+            let Some((_, common_dir)) = crate::git::git_dirs(worktree_path) else {
+                return in_worktree();
+            };
+            let name = match branch {
+                Some(branch) => branch.to_string(),
+                None => worktree_path
+                    .file_name()
+                    .map_or("detached".to_string(), |n| n.to_string_lossy().into_owned()),
+            };
+            crate::state::data_dir()
+                .join("notes")
+                .join(repo_key(&common_dir))
+                .join(format!("{}.md", name))
+        }
+    }
+}
+
+/// Directory name for a repo in the data dir: its path with `/` replaced by `%`
+pub fn repo_key(common_dir: &Path) -> String {
+    let repo = if common_dir.file_name().is_some_and(|n| n == ".git") {
+        common_dir.parent().unwrap_or(common_dir)
+    } else {
+        common_dir // bare repo
+    };
+    repo.to_string_lossy().replace(['/', '\\'], "%")
+}
+
+/// Whether a status file is left in the worktree root although notes are
+/// kept at `status_path`, which doesn't exist yet
+pub fn needs_migration(worktree_path: &Path, status_path: &Path) -> bool {
+    let old_path = worktree_path.join(STATUS_FILE);
+    old_path != status_path && old_path.exists() && !status_path.exists()
+}
+
+/// Move a status file left in the worktree root to `status_path`, unless one
+/// already exists there. Returns whether a file was moved. Only done on
+/// request (`wtm migrate-notes`), since the file may be tracked in git.
+pub fn migrate_status_file(worktree_path: &Path, status_path: &Path) -> io::Result<bool> {
+    if !needs_migration(worktree_path, status_path) {
+        return Ok(false);
+    }
+    let old_path = worktree_path.join(STATUS_FILE);
+
+    if let Some(parent) = status_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    // Copy and remove rather than rename, the data dir may be on another filesystem
+    std::fs::copy(&old_path, status_path)?;
+    std::fs::remove_file(&old_path)?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_repo_key() {
+        assert_eq!(repo_key(Path::new("/home/me/src/app/.git")), "%home%me%src%app");
+        assert_eq!(repo_key(Path::new("/srv/app.git")), "%srv%app.git");
+    }
+}
@@ -0,0 +1,135 @@
+use serde::{Deserialize, Serialize};
+
+/// A checklist line (`- [ ]` / `- [x]`) in a status file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Task {
+    pub line: usize,  // 0-based line number in the file
+    pub depth: usize, // nesting level, from the indentation
+    pub checked: bool,
+    pub text: String,
+}
+
+/// Find all checklist lines, at any indentation
+pub fn find_tasks(content: &str) -> Vec<Task> {
+    content
+        .lines()
+        .enumerate()
+        .filter_map(|(line, raw)| {
+            let trimmed = raw.trim();
+            let (checked, text) = if let Some(text) = trimmed
+                .strip_prefix("- [x]")
+                .or_else(|| trimmed.strip_prefix("- [X]"))
+            {
+                (true, text)
+            } else if let Some(text) = trimmed.strip_prefix("- [ ]") {
+                (false, text)
+            } else {
+                return None;
+            };
+            let indent: usize = raw
+                .chars()
+                .take_while(|c| c.is_whitespace())
+                .map(|c| if c == '\t' { 4 } else { 1 })
+                .sum();
+            Some(Task {
+                line,
+                depth: indent / 2,
+                checked,
+                text: text.trim().to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Flip the checkbox on the given line, leaving everything else untouched
+pub fn toggle_task(content: &str, line: usize) -> String {
+    let mut lines: Vec<String> = content.split('\n').map(str::to_string).collect();
+
+    if let Some(raw) = lines.get_mut(line) {
+        let indent = raw.len() - raw.trim_start().len();
+        let rest = &raw[indent..];
+        let toggled = if rest.starts_with("- [ ]") {
+            Some("- [x]")
+        } else if rest.starts_with("- [x]") || rest.starts_with("- [X]") {
+            Some("- [ ]")
+        } else {
+            None
+        };
+        if let Some(marker) = toggled {
+            raw.replace_range(indent..indent + 5, marker);
+        }
+    }
+
+    lines.join("\n")
+}
+
+/// Add an unchecked task at the end of the `## Status` section,
+/// creating the section at the end of the file if it is missing
+pub fn append_task(content: &str, text: &str) -> String {
+    let new_task = format!("- [ ] {}", text.trim());
+    let mut lines: Vec<&str> = content.split('\n').collect();
+
+    let Some(heading) = lines.iter().position(|l| l.trim_end() == "## Status") else {
+        let mut content = content.trim_end().to_string();
+        if !content.is_empty() {
+            content.push_str("\n\n");
+        }
+        content.push_str(&format!("## Status\n{}\n", new_task));
+        return content;
+    };
+
+    let section_end = lines[heading + 1..]
+        .iter()
+        .position(|l| l.starts_with("## "))
+        .map_or(lines.len(), |i| heading + 1 + i);
+
+    // After the last task in the section, or right below the heading
+    let insert_at = find_tasks(content)
+        .iter()
+        .rev()
+        .find(|t| t.line > heading && t.line < section_end)
+        .map_or(heading + 1, |t| t.line + 1);
+
+    lines.insert(insert_at, &new_task);
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTENT: &str = "# Worktree: test\n\n## Status\n- [x] Done\n- [ ] Todo\n  - [X] Nested\n\n## Notes\nkeep me\n";
+
+    #[test]
+    fn test_find_tasks() {
+        let tasks = find_tasks(CONTENT);
+        assert_eq!(tasks.len(), 3);
+        assert_eq!(tasks[1].line, 4);
+        assert!(!tasks[1].checked);
+        assert_eq!(tasks[2].text, "Nested");
+    }
+
+    #[test]
+    fn test_toggle_task_preserves_content() {
+        let toggled = toggle_task(CONTENT, 4);
+        assert_eq!(toggled, CONTENT.replace("- [ ] Todo", "- [x] Todo"));
+        let toggled = toggle_task(CONTENT, 5);
+        assert_eq!(toggled, CONTENT.replace("  - [X] Nested", "  - [ ] Nested"));
+        assert_eq!(toggle_task(CONTENT, 0), CONTENT);
+    }
+
+    #[test]
+    fn test_append_task() {
+        let appended = append_task(CONTENT, "New one");
+        assert_eq!(
+            appended,
+            CONTENT.replace("  - [X] Nested\n", "  - [X] Nested\n- [ ] New one\n")
+        );
+
+        let appended = append_task("## Status\n\n## Notes\n", "First");
+        assert_eq!(appended, "## Status\n- [ ] First\n\n## Notes\n");
+
+        let appended = append_task("# Title\n", "Orphan");
+        assert_eq!(appended, "# Title\n\n## Status\n- [ ] Orphan\n");
+    }
+}
@@ -1,4 +1,7 @@
-use crate::git::WorktreeStatus;
+use std::collections::BTreeSet;
+use std::path::Path;
+
+use crate::config::Config;
 
 const STATUS_TEMPLATE: &str = r#"# Worktree: {branch_name}
 
@@ -20,75 +23,173 @@ const STATUS_TEMPLATE: &str = r#"# Worktree: {branch_name}
 
 "#;
 
-pub fn generate_status_file(branch_name: &str) -> String {
-    STATUS_TEMPLATE.replace("{branch_name}", branch_name)
+/// Name of the built-in template, also used as the fallback
+pub const DEFAULT_TEMPLATE: &str = "default";
+
+/// Repo-local templates: `<repo>/.wtm/templates/<name>.md`
+const TEMPLATE_DIR: &str = ".wtm/templates";
+
+/// Values substituted into `{placeholders}` when generating a status file
+pub struct TemplateVars<'a> {
+    pub branch: &'a str,
+    pub base_branch: &'a str,
+    pub author: &'a str,
 }
 
-pub fn parse_status_file(content: &str) -> WorktreeStatus {
-    let mut status = WorktreeStatus::default();
-    let mut checked = 0u32;
-    let mut total = 0u32;
-
-    // Parse checkboxes
-    for line in content.lines() {
-        let trimmed = line.trim();
-        if trimmed.starts_with("- [x]") || trimmed.starts_with("- [X]") {
-            checked += 1;
-            total += 1;
-        } else if trimmed.starts_with("- [ ]") {
-            total += 1;
+/// All template names: the built-in one, inline ones from config and repo files
+pub fn list_templates(repo_path: &Path, config: &Config) -> Vec<String> {
+    let mut names: BTreeSet<String> = config.templates.keys().cloned().collect();
+    names.insert(DEFAULT_TEMPLATE.to_string());
+
+    if let Ok(entries) = std::fs::read_dir(repo_path.join(TEMPLATE_DIR)) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().is_some_and(|e| e == "md") {
+                if let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
+                    names.insert(stem.to_string());
+                }
+            }
         }
     }
 
-    status.progress = (checked, total);
+    names.into_iter().collect()
+}
 
-    // Extract purpose (first non-empty, non-comment line after "## Purpose")
-    let mut in_purpose = false;
-    for line in content.lines() {
-        if line.starts_with("## Purpose") {
-            in_purpose = true;
-            continue;
-        }
-        if in_purpose {
-            let trimmed = line.trim();
-            if !trimmed.is_empty() && !trimmed.starts_with("<!--") {
-                status.purpose = Some(trimmed.to_string());
-                break;
-            }
-            if line.starts_with("## ") {
-                break;
-            }
+/// Template for a branch: the first matching `template_rules` pattern, then a
+/// template named after the branch prefix (`fix/crash` -> `fix`), then `default`
+pub fn select_template(branch: &str, templates: &[String], config: &Config) -> String {
+    if let Some(rule) = config
+        .template_rules
+        .iter()
+        .find(|r| glob_match(&r.pattern, branch))
+    {
+        return rule.template.clone();
+    }
+
+    if let Some((prefix, _)) = branch.split_once('/') {
+        if templates.iter().any(|t| t == prefix) {
+            return prefix.to_string();
         }
     }
 
-    status
+    DEFAULT_TEMPLATE.to_string()
+}
+
+/// Template text by name. Repo files win over config, which wins over the built-in.
+pub fn load_template(name: &str, repo_path: &Path, config: &Config) -> String {
+    let file = repo_path.join(TEMPLATE_DIR).join(format!("{}.md", name));
+    if let Ok(content) = std::fs::read_to_string(file) {
+        return content;
+    }
+    config
+        .templates
+        .get(name)
+        .cloned()
+        .unwrap_or_else(|| STATUS_TEMPLATE.to_string())
+}
+
+pub fn generate_status_file(template: &str, vars: &TemplateVars) -> String {
+    let ticket = ticket_from_branch(vars.branch).unwrap_or_default();
+    let date = chrono::Local::now().format("%Y-%m-%d").to_string();
+
+    template
+        .replace("{branch_name}", vars.branch)
+        .replace("{base_branch}", vars.base_branch)
+        .replace("{author}", vars.author)
+        .replace("{date}", &date)
+        .replace("{ticket}", &ticket)
+}
+
+/// Ticket id in a branch name: `feature/abc-123-login` -> `ABC-123`,
+/// `fix/482-crash` -> `#482`
+pub fn ticket_from_branch(branch: &str) -> Option<String> {
+    let is_word = |p: &str| !p.is_empty() && p.chars().all(|c| c.is_ascii_alphabetic());
+    let is_number = |p: &str| !p.is_empty() && p.chars().all(|c| c.is_ascii_digit());
+
+    // Look within each path component so `fix/482` isn't read as `FIX-482`
+    let components: Vec<Vec<&str>> = branch
+        .split('/')
+        .map(|c| c.split(['-', '_']).collect())
+        .collect();
+
+    let keyed = components
+        .iter()
+        .flat_map(|parts| parts.windows(2))
+        .find(|w| is_word(w[0]) && is_number(w[1]));
+    if let Some(w) = keyed {
+        return Some(format!("{}-{}", w[0].to_uppercase(), w[1]));
+    }
+
+    components
+        .iter()
+        .flatten()
+        .find(|p| is_number(p))
+        .map(|n| format!("#{}", n))
+}
+
+/// Match a branch against a pattern where `*` matches any run of characters
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let Some((first, rest)) = pattern.split_once('*') else {
+        return pattern == text;
+    };
+    let Some(mut remaining) = text.strip_prefix(first) else {
+        return false;
+    };
+
+    let pieces: Vec<&str> = rest.split('*').collect();
+    let (last, middle) = pieces.split_last().expect("split yields at least one piece");
+    for piece in middle {
+        match remaining.find(piece) {
+            Some(i) => remaining = &remaining[i + piece.len()..],
+            None => return false,
+        }
+    }
+    remaining.len() >= last.len() && remaining.ends_with(last)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::TemplateRule;
 
     #[test]
-    fn test_parse_progress() {
-        let content = r#"# Worktree: test
-## Status
-- [x] Done
-- [ ] Not done
-- [X] Also done
-"#;
-        let status = parse_status_file(content);
-        assert_eq!(status.progress, (2, 3));
+    fn test_ticket_from_branch() {
+        assert_eq!(ticket_from_branch("feature/abc-123-login"), Some("ABC-123".to_string()));
+        assert_eq!(ticket_from_branch("fix/482-crash"), Some("#482".to_string()));
+        assert_eq!(ticket_from_branch("alice/spike"), None);
     }
 
     #[test]
-    fn test_parse_purpose() {
-        let content = r#"# Worktree: test
-## Purpose
-Implement OAuth2 authentication
+    fn test_glob_match() {
+        assert!(glob_match("fix/*", "fix/crash"));
+        assert!(glob_match("*/hotfix-*", "release/hotfix-2"));
+        assert!(glob_match("main", "main"));
+        assert!(!glob_match("fix/*", "feature/fix"));
+        assert!(!glob_match("ab*ba", "aba"));
+    }
 
-## Status
-"#;
-        let status = parse_status_file(content);
-        assert_eq!(status.purpose, Some("Implement OAuth2 authentication".to_string()));
+    #[test]
+    fn test_select_template() {
+        let mut config = Config::default();
+        config.template_rules.push(TemplateRule {
+            pattern: "release/*".to_string(),
+            template: "release".to_string(),
+        });
+        let templates = vec!["default".to_string(), "fix".to_string()];
+
+        assert_eq!(select_template("release/1.2", &templates, &config), "release");
+        assert_eq!(select_template("fix/crash", &templates, &config), "fix");
+        assert_eq!(select_template("feature/x", &templates, &config), "default");
+    }
+
+    #[test]
+    fn test_generate_status_file() {
+        let vars = TemplateVars {
+            branch: "fix/482-crash",
+            base_branch: "main",
+            author: "Alice",
+        };
+        let content = generate_status_file("{branch_name} {ticket} {base_branch} {author}", &vars);
+        assert_eq!(content, "fix/482-crash #482 main Alice");
     }
 }
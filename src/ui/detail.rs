@@ -1,44 +1,45 @@
 use ratatui::{
-    layout::Rect,
+    layout::{Margin, Rect},
     style::{Color, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph, Wrap},
+    widgets::{Block, Borders, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState, Wrap},
     Frame,
 };
 
-use crate::app::{App, DetailViewMode};
+use crate::app::{App, AppMode, DetailViewMode, DiffTarget, ListRow};
+use crate::git::Worktree;
 
 pub fn render(frame: &mut Frame, app: &App, area: Rect) {
+    if app.mode == AppMode::EditingSection {
+        render_editor(frame, app, area);
+        return;
+    }
+
+    let mut highlighted = None;
+    let mut wrap = true; // diffs are left unwrapped, like in a pager
     let (title, content) = if let Some(wt) = app.selected_worktree() {
         match app.detail_view {
+            _ if wt.is_bare => (" Bare repository ", bare_lines(wt)),
             DetailViewMode::Notes => {
-                let title = " Notes [t:git] ";
+                let task_line = app.task_cursor_line();
+                let title = if app.status_content.is_some() && task_line.is_some() {
+                    " Tasks [space:toggle a:add esc:back] "
+                } else {
+                    " Notes [t:git] "
+                };
                 let lines = if let Some(status_content) = &app.status_content {
-                    // Render status file with basic syntax highlighting
-                    status_content
-                        .lines()
-                        .map(|line| {
-                            if line.starts_with("# ") {
-                                Line::from(Span::styled(line, Style::default().fg(Color::Cyan)))
-                            } else if line.starts_with("## ") {
-                                Line::from(Span::styled(line, Style::default().fg(Color::Yellow)))
-                            } else if line.starts_with("- [x]") || line.starts_with("- [X]") {
-                                Line::from(Span::styled(line, Style::default().fg(Color::Green)))
-                            } else if line.starts_with("- [ ]") {
-                                Line::from(Span::styled(line, Style::default().fg(Color::Red)))
-                            } else if line.starts_with("<!--") || line.ends_with("-->") {
-                                Line::from(Span::styled(line, Style::default().fg(Color::DarkGray)))
-                            } else {
-                                Line::from(line)
-                            }
-                        })
-                        .collect()
+                    let (notes, cursor_row) =
+                        super::markdown::render(status_content, task_line);
+                    let mut lines = metadata_lines(wt);
+                    highlighted = cursor_row.map(|row| row + lines.len());
+                    lines.extend(notes);
+                    lines
                 } else {
                     // No status file
                     vec![
                         Line::from(""),
                         Line::from(Span::styled(
-                            "  No .worktree-status.md file",
+                            "  No status file",
                             Style::default().fg(Color::DarkGray),
                         )),
                         Line::from(""),
@@ -53,60 +54,354 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
                             wt.branch.as_deref().unwrap_or("(detached)")
                         )),
                         Line::from(format!("  Commit: {}", wt.commit)),
+                        Line::from(format!("  Upstream: {}", upstream_summary(wt))),
+                        Line::from(""),
+                        Line::from(format!("  Last commit:   {}", activity_summary(wt.last_commit_time))),
+                        Line::from(format!("  Last modified: {}", activity_summary(wt.last_modified))),
+                        Line::from(format!("  Last entered:  {}", activity_summary(wt.last_entered))),
                     ]
                 };
                 (title, lines)
             }
+            DetailViewMode::GitStatus
+                if matches!(
+                    app.mode,
+                    AppMode::Changes | AppMode::ConfirmDiscard | AppMode::Committing
+                ) =>
+            {
+                if let Some(diff) = &app.file_diff {
+                    wrap = false;
+                    let title = match &app.diff_target {
+                        Some(DiffTarget::Branch) => " Branch diff [n/N:hunk w:words esc:back] ",
+                        _ => " Diff [n/N:hunk w:words esc:back] ",
+                    };
+                    (title, super::diff::render(diff, app.word_diff))
+                } else {
+                    highlighted = (!app.changed_files.is_empty()).then_some(app.file_cursor);
+                    (
+                        " Changes [space:stage x:discard c:commit enter:diff b:branch esc:back] ",
+                        changed_file_lines(app, true),
+                    )
+                }
+            }
             DetailViewMode::GitStatus => {
-                let title = " Git Status [t:notes] ";
-                let lines = if let Some(status_content) = &app.status_content {
-                    status_content
-                        .lines()
-                        .map(|line| {
-                            if line.starts_with("M ") || line.starts_with(" M") {
-                                // Modified
-                                Line::from(Span::styled(line, Style::default().fg(Color::Yellow)))
-                            } else if line.starts_with("A ") || line.starts_with("?? ") {
-                                // Added / Untracked
-                                Line::from(Span::styled(line, Style::default().fg(Color::Green)))
-                            } else if line.starts_with("D ") || line.starts_with(" D") {
-                                // Deleted
-                                Line::from(Span::styled(line, Style::default().fg(Color::Red)))
-                            } else if line.starts_with("R ") {
-                                // Renamed
-                                Line::from(Span::styled(line, Style::default().fg(Color::Cyan)))
-                            } else if line == "Working tree clean" {
-                                Line::from(Span::styled(
-                                    format!("  {}", line),
-                                    Style::default().fg(Color::Green),
-                                ))
-                            } else {
-                                Line::from(format!(" {}", line))
-                            }
-                        })
-                        .collect()
+                let mut lines = vec![Line::from(
+                    [Span::raw(" ")]
+                        .into_iter()
+                        .chain(super::list::change_spans(&wt.changes, false))
+                        .collect::<Vec<_>>(),
+                )];
+                lines.extend(changed_file_lines(app, false));
+                (" Git Status [D:diff t:log] ", lines)
+            }
+            DetailViewMode::Log => {
+                if let Some(diff) = app.commit_diff.as_deref().filter(|_| app.mode == AppMode::Log) {
+                    wrap = false;
+                    (" Commit [n/N:hunk esc:back] ", super::diff::render(diff, false))
                 } else {
-                    vec![Line::from(Span::styled(
-                        "  Unable to get git status",
-                        Style::default().fg(Color::Red),
-                    ))]
-                };
-                (title, lines)
+                    let browsing = app.mode == AppMode::Log;
+                    if browsing {
+                        highlighted = Some(app.log_cursor);
+                    }
+                    let title = if browsing {
+                        " Log [enter:diff esc:back] "
+                    } else {
+                        " Log [L:browse t:notes] "
+                    };
+                    (title, log_lines(app, browsing))
+                }
             }
         }
     } else {
+        let on_group = app
+            .list_state
+            .selected()
+            .and_then(|row| app.rows.get(row))
+            .is_some_and(|row| matches!(row, ListRow::Group { .. }));
         (
             " Status ",
             vec![Line::from(Span::styled(
-                "  No worktrees found",
+                if app.worktrees.is_empty() {
+                    "  No worktrees found"
+                } else if on_group {
+                    "  Group header (Space: collapse/expand)"
+                } else {
+                    "  No matches"
+                },
                 Style::default().fg(Color::DarkGray),
             ))],
         )
     };
 
-    let paragraph = Paragraph::new(content)
-        .block(Block::default().borders(Borders::ALL).title(title))
-        .wrap(Wrap { trim: false });
+    let mut block = Block::default().borders(Borders::ALL).title(title);
+    if let Some(wt) = app.selected_worktree() {
+        if wt.branch.is_some() {
+            block = block.title_bottom(Line::from(Span::styled(
+                format!(" {} ", upstream_summary(wt)),
+                Style::default().fg(Color::Blue),
+            )));
+        }
+        block = block.title_bottom(
+            Line::from(Span::styled(
+                format!(" {} ", activity_ages(wt)),
+                Style::default().fg(Color::DarkGray),
+            ))
+            .right_aligned(),
+        );
+    }
+
+    let inner = block.inner(area);
+
+    // Wrapped row of the focused task, to keep it in view while checking off tasks
+    let highlighted = highlighted.map(|row| {
+        Paragraph::new(content[..row].to_vec())
+            .wrap(Wrap { trim: false })
+            .line_count(inner.width.saturating_sub(1)) as u16
+    });
+    let paragraph = Paragraph::new(content);
+    let paragraph = if wrap {
+        paragraph.wrap(Wrap { trim: false })
+    } else {
+        paragraph
+    };
+
+    // Clamp scrolling to the wrapped content height and remember the limits
+    // so the key handlers can page without overshooting
+    let total = paragraph.line_count(inner.width.saturating_sub(1)) as u16;
+    let max_scroll = total.saturating_sub(inner.height);
+    let scroll = match highlighted {
+        Some(row) => row.saturating_sub(inner.height / 2).min(max_scroll),
+        None => app.detail_scroll.min(max_scroll),
+    };
+    app.detail_max_scroll.set(max_scroll);
+    app.detail_page.set(inner.height.saturating_sub(2).max(1));
+
+    frame.render_widget(block, area);
+    frame.render_widget(
+        paragraph.scroll((scroll, 0)),
+        Rect {
+            width: inner.width.saturating_sub(1),
+            ..inner
+        },
+    );
+
+    if max_scroll > 0 {
+        let mut state = ScrollbarState::new(max_scroll as usize).position(scroll as usize);
+        frame.render_stateful_widget(
+            Scrollbar::new(ScrollbarOrientation::VerticalRight),
+            area.inner(Margin {
+                vertical: 1,
+                horizontal: 0,
+            }),
+            &mut state,
+        );
+    }
+}
+
+/// `git status` style list of changed files, optionally with the cursor row highlighted
+fn changed_file_lines(app: &App, with_cursor: bool) -> Vec<Line<'static>> {
+    if app.changed_files.is_empty() && with_cursor {
+        return vec![Line::from(Span::styled(
+            "  Working tree clean (b: branch diff)",
+            Style::default().fg(Color::Green),
+        ))];
+    }
+
+    app.changed_files
+        .iter()
+        .enumerate()
+        .map(|(i, file)| {
+            let color = |status: char| match status {
+                'M' => Color::Yellow,
+                'A' | '?' => Color::Green,
+                'D' => Color::Red,
+                'R' | 'C' => Color::Cyan,
+                _ => Color::DarkGray,
+            };
+            let mut line = Line::from(vec![
+                Span::styled(format!(" {}", file.staged), Style::default().fg(color(file.staged))),
+                Span::styled(format!("{} ", file.unstaged), Style::default().fg(color(file.unstaged))),
+                Span::raw(file.path.clone()),
+            ]);
+            if with_cursor && i == app.file_cursor {
+                line = line.style(Style::default().bg(Color::DarkGray));
+            }
+            line
+        })
+        .collect()
+}
+
+/// One line per commit ahead of the main branch: hash, age, author, subject
+fn log_lines(app: &App, browsing: bool) -> Vec<Line<'static>> {
+    if app.commits.is_empty() {
+        return vec![Line::from(Span::styled(
+            "  No commits ahead of the main branch",
+            Style::default().fg(Color::DarkGray),
+        ))];
+    }
+
+    let now = crate::age::now();
+    app.commits
+        .iter()
+        .enumerate()
+        .map(|(i, commit)| {
+            let mut line = Line::from(vec![
+                Span::styled(format!(" {} ", commit.short_hash), Style::default().fg(Color::Yellow)),
+                Span::styled(
+                    format!("{:>3} ", crate::age::format_age(commit.time, now)),
+                    Style::default().fg(Color::DarkGray),
+                ),
+                Span::styled(format!("{} ", commit.author), Style::default().fg(Color::Blue)),
+                Span::raw(commit.subject.clone()),
+            ]);
+            if browsing && i == app.log_cursor {
+                line = line.style(Style::default().bg(Color::DarkGray));
+            }
+            line
+        })
+        .collect()
+}
+
+fn activity_summary(timestamp: Option<i64>) -> String {
+    match timestamp {
+        Some(t) => format!(
+            "{} ({} ago)",
+            crate::age::format_date(t),
+            crate::age::format_age(t, crate::age::now())
+        ),
+        None => "never".to_string(),
+    }
+}
+
+/// Compact "commit 3d · modified 2h · entered 1w" summary for the border
+fn activity_ages(wt: &Worktree) -> String {
+    let now = crate::age::now();
+    [
+        ("commit", wt.last_commit_time),
+        ("modified", wt.last_modified),
+        ("entered", wt.last_entered),
+    ]
+    .into_iter()
+    .filter_map(|(label, t)| t.map(|t| format!("{} {}", label, crate::age::format_age(t, now))))
+    .collect::<Vec<_>>()
+    .join(" · ")
+}
+
+/// Frontmatter fields and per-section progress, shown above the notes
+fn metadata_lines(wt: &Worktree) -> Vec<Line<'static>> {
+    let meta = &wt.status.meta;
+    let mut spans = Vec::new();
+
+    if let Some(state) = &meta.state {
+        let color = match state.to_lowercase().as_str() {
+            "wip" => Color::Yellow,
+            "review" => Color::Cyan,
+            "blocked" => Color::Red,
+            "done" => Color::Green,
+            _ => Color::Magenta,
+        };
+        spans.push(Span::styled(
+            format!(" {} ", state),
+            Style::default().fg(Color::Black).bg(color),
+        ));
+        spans.push(Span::raw(" "));
+    }
+    for (label, value) in [
+        ("owner", &meta.owner),
+        ("ticket", &meta.ticket),
+        ("priority", &meta.priority),
+    ] {
+        if let Some(value) = value {
+            spans.push(Span::styled(format!("{}: ", label), Style::default().fg(Color::DarkGray)));
+            spans.push(Span::raw(format!("{}  ", value)));
+        }
+    }
+    if let Some(due) = &meta.due {
+        let overdue = meta.is_overdue(chrono::Local::now().date_naive());
+        spans.push(Span::styled("due: ", Style::default().fg(Color::DarkGray)));
+        spans.push(Span::styled(
+            format!("{}  ", due),
+            Style::default().fg(if overdue { Color::Red } else { Color::Reset }),
+        ));
+    }
+    for tag in &meta.tags {
+        spans.push(Span::styled(format!("#{} ", tag), Style::default().fg(Color::Blue)));
+    }
+
+    let mut lines = Vec::new();
+    if !spans.is_empty() {
+        lines.push(Line::from(spans));
+    }
+
+    // Only worth a line when tasks are spread over several sections
+    let with_tasks: Vec<_> = wt.status.sections.iter().filter(|s| s.progress.1 > 0).collect();
+    if with_tasks.len() > 1 {
+        let summary: Vec<String> = with_tasks
+            .iter()
+            .map(|s| format!("{} {}/{}", s.title, s.progress.0, s.progress.1))
+            .collect();
+        lines.push(Line::from(Span::styled(
+            summary.join(" · "),
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+
+    if !lines.is_empty() {
+        lines.push(Line::default());
+    }
+    lines
+}
+
+/// Inline editor for a status file section, scrolled to keep the cursor visible
+fn render_editor(frame: &mut Frame, app: &App, area: Rect) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .title(format!(" Edit {} ", app.editing_section))
+        .title_bottom(Line::from(Span::styled(
+            " esc/ctrl+s:save ctrl+c:cancel ctrl+z:undo ctrl+w:delete word ",
+            Style::default().fg(Color::DarkGray),
+        )));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let (row, col) = app.editor.cursor();
+    let cursor_x = super::cursor_column(&app.editor.lines()[row], col);
+    let scroll_y = (row as u16).saturating_sub(inner.height.saturating_sub(1));
+    let scroll_x = cursor_x.saturating_sub(inner.width.saturating_sub(1));
+
+    let lines: Vec<Line> = app
+        .editor
+        .lines()
+        .iter()
+        .map(|l| Line::from(l.as_str()))
+        .collect();
+    frame.render_widget(Paragraph::new(lines).scroll((scroll_y, scroll_x)), inner);
+    frame.set_cursor_position((
+        inner.x + cursor_x - scroll_x,
+        inner.y + row as u16 - scroll_y,
+    ));
+}
+
+/// The bare repository of a bare + worktrees layout: nothing checked out
+fn bare_lines(wt: &Worktree) -> Vec<Line<'static>> {
+    let dim = Style::default().fg(Color::DarkGray);
+    vec![
+        Line::from(""),
+        Line::from(Span::styled("  Bare repository, no checkout", dim)),
+        Line::from(""),
+        Line::from(Span::styled("  Worktrees are created from it, select one", dim)),
+        Line::from(Span::styled("  and press 'M' to make it the home worktree", dim)),
+        Line::from(""),
+        Line::from(format!("  Path: {}", wt.path.display())),
+    ]
+}
 
-    frame.render_widget(paragraph, area);
+fn upstream_summary(wt: &Worktree) -> String {
+    match &wt.upstream {
+        None => "not pushed (p: push)".to_string(),
+        Some(u) if u.gone => format!("{} (gone)", u.name),
+        Some(u) if u.ahead == 0 && u.behind == 0 => format!("{} (up to date)", u.name),
+        Some(u) => format!("{} (↑{} ↓{})", u.name, u.ahead, u.behind),
+    }
 }
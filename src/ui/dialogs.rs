@@ -1,12 +1,13 @@
 use ratatui::{
-    layout::{Constraint, Direction, Layout, Rect},
+    layout::{Constraint, Direction, Layout, Margin, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Clear, List, ListItem, Paragraph},
+    widgets::{Block, Borders, Clear, List, ListItem, Paragraph, Wrap},
     Frame,
 };
 
 use crate::app::App;
+use crate::archive::ArchiveEntry;
 
 fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
     let popup_layout = Layout::default()
@@ -48,6 +49,7 @@ pub fn render_create_dialog(frame: &mut Frame, app: &App) {
             Constraint::Length(3), // Input
             Constraint::Length(1), // Suggestions label
             Constraint::Min(0),    // Suggestions list
+            Constraint::Length(1), // Template
             Constraint::Length(1), // Help
         ])
         .margin(1)
@@ -107,16 +109,384 @@ pub fn render_create_dialog(frame: &mut Frame, app: &App) {
         frame.render_widget(new_branch_hint, chunks[2]);
     }
 
+    // Status file template
+    let (template, auto) = app.create_template();
+    let template_line = Paragraph::new(Line::from(vec![
+        Span::raw("Template: "),
+        Span::styled(template, Style::default().fg(Color::Cyan)),
+        Span::styled(
+            if auto { " (from branch name)" } else { "" },
+            Style::default().fg(Color::DarkGray),
+        ),
+    ]));
+    frame.render_widget(template_line, chunks[4]);
+
     // Help text
     let help = Paragraph::new(Line::from(vec![
         Span::styled("Enter", Style::default().add_modifier(Modifier::BOLD)),
         Span::raw(": create  "),
+        Span::styled("↑/↓", Style::default().add_modifier(Modifier::BOLD)),
+        Span::raw(": template  "),
         Span::styled("Tab", Style::default().add_modifier(Modifier::BOLD)),
         Span::raw(": autocomplete  "),
         Span::styled("Esc", Style::default().add_modifier(Modifier::BOLD)),
         Span::raw(": cancel"),
     ]));
-    frame.render_widget(help, chunks[4]);
+    frame.render_widget(help, chunks[5]);
+}
+
+pub fn render_add_task_dialog(frame: &mut Frame, app: &App) {
+    let area = centered_rect(60, 20, frame.area());
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(" Add Task ")
+        .style(Style::default().bg(Color::Black));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Input
+            Constraint::Min(0),
+            Constraint::Length(1), // Help
+        ])
+        .split(inner);
+
+    let input_block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+    let input = Paragraph::new(app.input.as_str()).block(input_block);
+    frame.render_widget(input, chunks[0]);
+
+    let cursor_x = chunks[0].x + 1 + super::cursor_column(&app.input, app.input_cursor);
+    let cursor_y = chunks[0].y + 1;
+    frame.set_cursor_position((cursor_x, cursor_y));
+
+    let help = Paragraph::new(Line::from(vec![
+        Span::styled("Enter", Style::default().add_modifier(Modifier::BOLD)),
+        Span::raw(": add to ## Status  "),
+        Span::styled("Esc", Style::default().add_modifier(Modifier::BOLD)),
+        Span::raw(": cancel"),
+    ]));
+    frame.render_widget(help, chunks[2]);
+}
+
+pub fn render_commit_dialog(frame: &mut Frame, app: &App) {
+    let area = centered_rect(60, 20, frame.area());
+    frame.render_widget(Clear, area);
+
+    let staged = app
+        .changed_files
+        .iter()
+        .filter(|f| f.is_staged())
+        .count();
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" Commit {} staged file(s) ", staged))
+        .style(Style::default().bg(Color::Black));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Input
+            Constraint::Min(0),
+            Constraint::Length(1), // Help
+        ])
+        .split(inner);
+
+    let input_block = Block::default()
+        .borders(Borders::ALL)
+        .title(" Message ")
+        .border_style(Style::default().fg(Color::Cyan));
+    let input = Paragraph::new(app.input.as_str()).block(input_block);
+    frame.render_widget(input, chunks[0]);
+
+    let cursor_x = chunks[0].x + 1 + super::cursor_column(&app.input, app.input_cursor);
+    let cursor_y = chunks[0].y + 1;
+    frame.set_cursor_position((cursor_x, cursor_y));
+
+    let help = Paragraph::new(Line::from(vec![
+        Span::styled("Enter", Style::default().add_modifier(Modifier::BOLD)),
+        Span::raw(": commit  "),
+        Span::styled("Esc", Style::default().add_modifier(Modifier::BOLD)),
+        Span::raw(": cancel"),
+    ]));
+    frame.render_widget(help, chunks[2]);
+}
+
+pub fn render_discard_dialog(frame: &mut Frame, app: &App) {
+    let area = centered_rect(55, 35, frame.area());
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(" Discard Changes ")
+        .style(Style::default().bg(Color::Black));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let Some(file) = app.changed_files.get(app.file_cursor) else {
+        return;
+    };
+    let what = match file.staged {
+        '?' | 'A' | 'R' | 'C' => "The new file will be deleted",
+        _ => "Staged and unstaged changes will be lost",
+    };
+    let lines = vec![
+        Line::from(""),
+        Line::from(format!(" {}", file.path)),
+        Line::from(""),
+        Line::from(Span::styled(format!(" {}", what), Style::default().fg(Color::Red))),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled(" Discard? ", Style::default().fg(Color::Red)),
+            Span::styled("y", Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)),
+            Span::raw("/"),
+            Span::styled("n", Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)),
+        ]),
+    ];
+    frame.render_widget(Paragraph::new(lines), inner);
+}
+
+pub fn render_abort_dialog(frame: &mut Frame, app: &App) {
+    let area = centered_rect(55, 35, frame.area());
+    frame.render_widget(Clear, area);
+
+    let Some(wt) = app.selected_worktree() else {
+        return;
+    };
+    let Some(op) = wt.operation else {
+        return;
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" Abort {} ", op.command()))
+        .style(Style::default().bg(Color::Black));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let lines = vec![
+        Line::from(""),
+        Line::from(format!(" Branch: {}", wt.branch.as_deref().unwrap_or("(detached)"))),
+        Line::from(format!(" Path: {}", wt.path.display())),
+        Line::from(""),
+        Line::from(Span::styled(
+            format!(" The {} is undone, including resolved conflicts", op.command()),
+            Style::default().fg(Color::Red),
+        )),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled(" Abort? ", Style::default().fg(Color::Red)),
+            Span::styled("y", Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)),
+            Span::raw("/"),
+            Span::styled("n", Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)),
+        ]),
+    ];
+    frame.render_widget(Paragraph::new(lines), inner);
+}
+
+pub fn render_stashes(frame: &mut Frame, app: &App) {
+    let area = centered_rect(90, 85, frame.area());
+    frame.render_widget(Clear, area);
+
+    let stashes = app.visible_stashes();
+    let branch = app
+        .selected_worktree()
+        .and_then(|w| w.branch.as_deref())
+        .unwrap_or("(detached)");
+    let title = if app.stash_all {
+        format!(" All stashes ({}) ", stashes.len())
+    } else {
+        format!(" Stashes on {} ({}/{}) ", branch, stashes.len(), app.stashes.len())
+    };
+    let hint = if app.confirm_drop {
+        Line::from(vec![
+            Span::styled(" Drop this stash? ", Style::default().fg(Color::Red)),
+            Span::styled("y", Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)),
+            Span::raw("/"),
+            Span::styled("n ", Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)),
+        ])
+    } else {
+        Line::from(Span::styled(
+            " s:stash changes p:pop a:apply d:drop Tab:all/branch PgUp/PgDn:scroll Esc:close ",
+            Style::default().fg(Color::DarkGray),
+        ))
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(title)
+        .title_bottom(hint)
+        .style(Style::default().bg(Color::Black));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+        .split(inner);
+
+    let now = crate::age::now();
+    let items: Vec<ListItem> = stashes
+        .iter()
+        .enumerate()
+        .map(|(i, stash)| {
+            let style = if i == app.stash_cursor {
+                Style::default().bg(Color::DarkGray).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            let mut spans = vec![
+                Span::styled(format!(" {} ", stash.name), Style::default().fg(Color::Yellow)),
+                Span::styled(
+                    format!("{:>3} ", crate::age::format_age(stash.time, now)),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ];
+            if app.stash_all {
+                let branch = stash.branch.as_deref().unwrap_or("(detached)");
+                spans.push(Span::styled(format!("{} ", branch), Style::default().fg(Color::Cyan)));
+            }
+            spans.push(Span::raw(stash.message.clone()));
+            ListItem::new(Line::from(spans)).style(style)
+        })
+        .collect();
+    frame.render_widget(
+        List::new(items).block(Block::default().borders(Borders::RIGHT)),
+        columns[0],
+    );
+
+    let lines = match &app.stash_diff {
+        Some(diff) => super::diff::render(diff, false),
+        None => vec![Line::from(Span::styled(
+            if app.stash_all {
+                "  No stashes"
+            } else {
+                "  No stashes on this branch (Tab: show all, s: stash changes)"
+            },
+            Style::default().fg(Color::DarkGray),
+        ))],
+    };
+    let diff_area = columns[1].inner(Margin { horizontal: 1, vertical: 0 });
+    let diff = Paragraph::new(lines);
+    let max_scroll = (diff.line_count(diff_area.width) as u16).saturating_sub(diff_area.height);
+    frame.render_widget(diff.scroll((app.stash_scroll.min(max_scroll), 0)), diff_area);
+}
+
+pub fn render_history(frame: &mut Frame, app: &App) {
+    let area = centered_rect(90, 85, frame.area());
+    frame.render_widget(Clear, area);
+
+    let matches = app.history_matches();
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" Archived worktrees ({}/{}) ", matches.len(), app.archive.entries.len()))
+        .title_bottom(Line::from(Span::styled(
+            " type:search ↑/↓:select PgUp/PgDn:scroll Esc:close ",
+            Style::default().fg(Color::DarkGray),
+        )))
+        .style(Style::default().bg(Color::Black));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Min(0)])
+        .split(inner);
+    let search = Paragraph::new(Line::from(vec![
+        Span::styled(" Search: ", Style::default().fg(Color::Cyan)),
+        Span::raw(app.history_query.as_str()),
+    ]));
+    frame.render_widget(search, rows[0]);
+    let query_width = super::cursor_column(&app.history_query, usize::MAX);
+    frame.set_cursor_position((rows[0].x + 9 + query_width, rows[0].y));
+
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+        .split(rows[1]);
+
+    let items: Vec<ListItem> = matches
+        .iter()
+        .enumerate()
+        .map(|(i, entry)| {
+            let (checked, total) = entry.progress;
+            let progress = if total > 0 {
+                format!("[{}/{}]", checked, total)
+            } else {
+                String::new()
+            };
+            let style = if i == app.history_cursor {
+                Style::default().bg(Color::DarkGray).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!(" {} ", crate::age::format_date(entry.archived_at)),
+                    Style::default().fg(Color::DarkGray),
+                ),
+                Span::raw(format!("{} ", entry.branch)),
+                Span::styled(progress, Style::default().fg(Color::Green)),
+            ]))
+            .style(style)
+        })
+        .collect();
+    frame.render_widget(
+        List::new(items).block(Block::default().borders(Borders::RIGHT)),
+        columns[0],
+    );
+
+    let lines = match matches.get(app.history_cursor) {
+        Some(entry) => archive_entry_lines(entry),
+        None => vec![Line::from(Span::styled(
+            "  No archived worktrees",
+            Style::default().fg(Color::DarkGray),
+        ))],
+    };
+    let notes_area = columns[1].inner(Margin { horizontal: 1, vertical: 0 });
+    let notes = Paragraph::new(lines).wrap(Wrap { trim: false });
+    let max_scroll = (notes.line_count(notes_area.width) as u16).saturating_sub(notes_area.height);
+    frame.render_widget(notes.scroll((app.history_scroll.min(max_scroll), 0)), notes_area);
+}
+
+fn archive_entry_lines(entry: &ArchiveEntry) -> Vec<Line<'static>> {
+    let dim = Style::default().fg(Color::DarkGray);
+    let last_commit = entry
+        .last_commit_time
+        .map_or("-".to_string(), crate::age::format_date);
+
+    let mut lines = vec![
+        Line::from(Span::styled(
+            entry.branch.clone(),
+            Style::default().add_modifier(Modifier::BOLD),
+        )),
+        Line::from(vec![
+            Span::styled("commit ", dim),
+            Span::raw(entry.commit.clone()),
+            Span::styled("  last commit ", dim),
+            Span::raw(last_commit),
+            Span::styled("  archived ", dim),
+            Span::raw(crate::age::format_date(entry.archived_at)),
+        ]),
+        Line::from(Span::styled(entry.path.display().to_string(), dim)),
+        Line::default(),
+    ];
+    match &entry.notes {
+        Some(notes) => lines.extend(super::markdown::render(notes, None).0),
+        None => lines.push(Line::from(Span::styled("No notes", dim))),
+    }
+    lines
 }
 
 pub fn render_delete_dialog(frame: &mut Frame, app: &App) {
@@ -241,7 +611,13 @@ pub fn render_help(frame: &mut Frame, app: &App) {
             Style::default().add_modifier(Modifier::BOLD),
         )),
         Line::from("  j/k, ↑/↓    Move selection"),
-        Line::from("  Tab         Toggle notes/git status view"),
+        Line::from("  Tab         Cycle notes/git status/log view"),
+        Line::from("  PgUp/PgDn   Scroll detail pane (or mouse wheel)"),
+        Line::from("  Space       Collapse/expand group"),
+        Line::from("  /           Filter list (Esc clears)"),
+        Line::from("              is:dirty is:merged is:ahead"),
+        Line::from("              has:notes has:stash progress:<50%"),
+        Line::from("              state:review owner:x tag:x is:overdue"),
         Line::from(""),
         Line::from(Span::styled(
             " Shortcuts (from config)",
@@ -260,8 +636,23 @@ pub fn render_help(frame: &mut Frame, app: &App) {
                     "create" => "Create new worktree".to_string(),
                     "delete" => "Delete worktree".to_string(),
                     "edit" => "Edit status file".to_string(),
+                    "tasks" => "Check off tasks in notes".to_string(),
+                    "edit_purpose" => "Edit purpose inline".to_string(),
+                    "edit_notes" => "Edit notes inline".to_string(),
                     "merge_main" => "Merge main (ff-only)".to_string(),
-                    "toggle_view" => "Toggle notes/git view".to_string(),
+                    "set_home" => "Make this the bare repository's home worktree".to_string(),
+                    "push" => "Push branch (sets upstream)".to_string(),
+                    "log" => "Browse branch commits and their diffs".to_string(),
+                    "diff" => "Stage, discard, commit and diff changed files".to_string(),
+                    "stashes" => "Stash, pop, apply and drop stashes".to_string(),
+                    "continue_operation" => "Continue a rebase, merge or cherry-pick".to_string(),
+                    "abort_operation" => "Abort a rebase, merge, cherry-pick or bisect".to_string(),
+                    "toggle_view" => "Cycle notes/git status/log view".to_string(),
+                    "sort" => "Cycle sort order".to_string(),
+                    "group" => "Toggle grouping by prefix".to_string(),
+                    "dashboard" => "Toggle worktrees of all repos".to_string(),
+                    "report" => "Copy a markdown report to the clipboard".to_string(),
+                    "history" => "Browse deleted worktrees' notes".to_string(),
                     "refresh" => "Refresh list".to_string(),
                     "help" => "Toggle this help".to_string(),
                     "quit" => "Quit".to_string(),
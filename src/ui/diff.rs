@@ -0,0 +1,103 @@
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+};
+
+/// Color a unified diff (`git show`/`git diff` output) line by line. With
+/// `word_diff`, `[-removed-]` and `{+added+}` runs within lines are highlighted.
+pub fn render(diff: &str, word_diff: bool) -> Vec<Line<'static>> {
+    let mut in_patch = false;
+    diff.lines()
+        .map(|line| {
+            if line.starts_with("diff --git") {
+                in_patch = true;
+            }
+            if !in_patch && line.starts_with(' ') && line.contains(" | ") {
+                render_stat_line(line)
+            } else if word_diff && in_patch && !is_header(line) {
+                render_word_diff_line(line)
+            } else {
+                render_line(line)
+            }
+        })
+        .collect()
+}
+
+fn is_header(line: &str) -> bool {
+    ["diff --git", "index ", "+++", "---", "@@", "new file", "deleted file", "similarity", "rename "]
+        .iter()
+        .any(|p| line.starts_with(p))
+}
+
+fn render_line(line: &str) -> Line<'static> {
+    let style = if line.starts_with("diff --git") || line.starts_with("commit ") {
+        Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+    } else if line.starts_with("+++") || line.starts_with("---") || line.starts_with("index ") {
+        Style::default().fg(Color::DarkGray)
+    } else if line.starts_with("@@") {
+        Style::default().fg(Color::Cyan)
+    } else if line.starts_with('+') {
+        Style::default().fg(Color::Green)
+    } else if line.starts_with('-') {
+        Style::default().fg(Color::Red)
+    } else if line == "Staged:" || line == "Unstaged:" {
+        Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD)
+    } else {
+        Style::default()
+    };
+    Line::from(Span::styled(line.to_string(), style))
+}
+
+/// ` src/app.rs | 12 +++++-------` with the bar colored
+fn render_stat_line(line: &str) -> Line<'static> {
+    let bar_start = line.trim_end_matches(['+', '-']).len();
+    let (text, bar) = line.split_at(bar_start);
+    let added = bar.chars().filter(|c| *c == '+').count();
+    Line::from(vec![
+        Span::raw(text.to_string()),
+        Span::styled("+".repeat(added), Style::default().fg(Color::Green)),
+        Span::styled("-".repeat(bar.len() - added), Style::default().fg(Color::Red)),
+    ])
+}
+
+/// Split a `--word-diff=plain` line into plain, removed and added spans
+fn render_word_diff_line(line: &str) -> Line<'static> {
+    let removed = Style::default().fg(Color::Red).add_modifier(Modifier::CROSSED_OUT);
+    let added = Style::default().fg(Color::Green).add_modifier(Modifier::BOLD);
+
+    let mut spans = Vec::new();
+    let mut rest = line;
+    loop {
+        let next = [("[-", "-]", removed), ("{+", "+}", added)]
+            .into_iter()
+            .filter_map(|(open, close, style)| rest.find(open).map(|i| (i, open, close, style)))
+            .min_by_key(|(i, ..)| *i);
+        let Some((start, open, close, style)) = next else {
+            break;
+        };
+        let Some(len) = rest[start + open.len()..].find(close) else {
+            break;
+        };
+
+        spans.push(Span::raw(rest[..start].to_string()));
+        let inner = &rest[start + open.len()..start + open.len() + len];
+        spans.push(Span::styled(inner.to_string(), style));
+        rest = &rest[start + open.len() + len + close.len()..];
+    }
+    spans.push(Span::raw(rest.to_string()));
+    Line::from(spans)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_word_diff_spans() {
+        let line = render_word_diff_line("let x = [-1-]{+2+};");
+        let text: Vec<&str> = line.spans.iter().map(|s| s.content.as_ref()).collect();
+        assert_eq!(text, vec!["let x = ", "1", "", "2", ";"]);
+        assert_eq!(line.spans[1].style.fg, Some(Color::Red));
+        assert_eq!(line.spans[3].style.fg, Some(Color::Green));
+    }
+}
@@ -6,7 +6,7 @@ use ratatui::{
     Frame,
 };
 
-use crate::app::App;
+use crate::app::{App, AppMode};
 
 pub struct AppLayout {
     pub header: Rect,
@@ -51,6 +51,23 @@ pub fn render_header(frame: &mut Frame, area: Rect) {
 }
 
 pub fn render_footer(frame: &mut Frame, app: &App, area: Rect) {
+    // Filter input takes over the footer while typing
+    if app.mode == AppMode::Filtering {
+        let input_line = Line::from(vec![
+            Span::styled(" /", Style::default().fg(Color::Cyan)),
+            Span::raw(app.filter_input.as_str()),
+        ]);
+        let keybindings = Line::from(vec![Span::styled(
+            " Enter:apply Esc:clear ↑/↓:move  is:dirty is:merged is:ahead has:notes progress:<50% ",
+            Style::default().fg(Color::DarkGray),
+        )]);
+        let footer = Paragraph::new(vec![input_line, keybindings]);
+        frame.render_widget(footer, area);
+        let cursor_x = super::cursor_column(&app.filter_input, app.filter_cursor);
+        frame.set_cursor_position((area.x + 2 + cursor_x, area.y));
+        return;
+    }
+
     // Show error if present
     if let Some(error) = &app.error {
         let error_line = Line::from(vec![
@@ -58,17 +75,38 @@ pub fn render_footer(frame: &mut Frame, app: &App, area: Rect) {
             Span::raw(error.as_str()),
         ]);
         let keybindings = Line::from(vec![Span::styled(
-            " n:new d:del e:edit g:git c:ide m:merge t:toggle r:refresh ?:help q:quit ",
+            " n:new d:del e:edit g:git c:ide m:merge p:push t:toggle r:refresh ?:help q:quit ",
             Style::default().fg(Color::DarkGray),
         )]);
         let footer = Paragraph::new(vec![error_line, keybindings]);
         frame.render_widget(footer, area);
+    } else if let Some(notice) = &app.notice {
+        let notice_line = Line::from(vec![Span::styled(
+            format!(" {}", notice),
+            Style::default().fg(Color::Green),
+        )]);
+        let keybindings = Line::from(vec![Span::styled(
+            " n:new d:del e:edit g:git c:ide m:merge p:push t:toggle r:refresh ?:help q:quit ",
+            Style::default().fg(Color::DarkGray),
+        )]);
+        let footer = Paragraph::new(vec![notice_line, keybindings]);
+        frame.render_widget(footer, area);
     } else {
         let keybindings = Line::from(vec![Span::styled(
-            " n:new d:del e:edit g:git c:ide m:merge t:toggle Enter:cd r:refresh ?:help q:quit ",
+            " n:new d:del e:edit x:tasks g:git c:ide m:merge p:push t:toggle /:filter Enter:cd r:refresh ?:help q:quit ",
             Style::default().fg(Color::DarkGray),
         )]);
-        let footer = Paragraph::new(vec![Line::default(), keybindings]);
+        // Keep an applied filter visible after leaving the input
+        let filter_line = if app.filter.is_empty() {
+            Line::default()
+        } else {
+            Line::from(vec![
+                Span::styled(" /", Style::default().fg(Color::Cyan)),
+                Span::raw(app.filter_input.as_str()),
+                Span::styled("  (Esc: clear)", Style::default().fg(Color::DarkGray)),
+            ])
+        };
+        let footer = Paragraph::new(vec![filter_line, keybindings]);
         frame.render_widget(footer, area);
     }
 }
@@ -6,87 +6,77 @@ use ratatui::{
     Frame,
 };
 
-use crate::app::App;
+use crate::app::{App, ListRow};
+use crate::git::{StatusCounts, Worktree};
+use crate::sort::SortMode;
 
 pub fn render(frame: &mut Frame, app: &App, area: Rect) {
-    let items: Vec<ListItem> = app
-        .worktrees
-        .iter()
-        .map(|wt| {
-            let (checked, total) = wt.status.progress;
-            let progress = if total > 0 {
-                format!("[{}/{}]", checked, total)
-            } else {
-                "[---]".to_string()
-            };
-
-            let branch_name = wt.branch.as_deref().unwrap_or("(detached)");
-            let main_marker = if wt.is_main { "(main)" } else { "" };
-
-            // Determine if branch is merged and ready to delete (ahead=0, clean, not main)
-            let is_merged = !wt.is_main && wt.ahead == 0 && !wt.has_changes;
+    let now = crate::age::now();
+    let stale_after = i64::from(app.config.stale_days) * 86400;
 
-            // Indicator: * for dirty, ✓ for merged, space otherwise
-            let indicator = if wt.has_changes {
-                "*"
-            } else if is_merged {
-                "✓"
-            } else {
-                " "
-            };
-
-            let display_name = if wt.is_main {
-                format!("{} {}", branch_name, main_marker)
-            } else {
-                branch_name.to_string()
-            };
+    // Number the first nine worktrees for quick jumping with 1-9, skipping the
+    // bare repository, which can't be entered
+    let mut number = 0;
 
-            // Build ahead/behind indicator for non-main branches
-            let ahead_behind = if !wt.is_main && (wt.ahead > 0 || wt.behind > 0) {
-                format!(" ↑{}↓{}", wt.ahead, wt.behind)
-            } else {
-                String::new()
-            };
-
-            // Color: green for main or merged branches, cyan for others
-            let branch_color = if wt.is_main || is_merged {
-                Color::Green
-            } else {
-                Color::Cyan
-            };
-
-            let line = Line::from(vec![
-                Span::styled(
-                    format!("{} ", indicator),
-                    if wt.has_changes {
-                        Style::default().fg(Color::Yellow)
-                    } else if is_merged {
-                        Style::default().fg(Color::Green)
-                    } else {
-                        Style::default()
-                    },
-                ),
-                Span::styled(
-                    format!("{:<24}", display_name),
-                    Style::default().fg(branch_color),
-                ),
-                Span::styled(
-                    ahead_behind,
-                    Style::default().fg(Color::Magenta),
-                ),
-                Span::styled(format!(" {}", progress), Style::default().fg(Color::Yellow)),
-            ]);
-
-            ListItem::new(line)
+    let items: Vec<ListItem> = app
+        .rows
+        .iter()
+        .map(|row| match row {
+            ListRow::Group {
+                name,
+                count,
+                collapsed,
+            } => {
+                let arrow = if *collapsed { "▸" } else { "▾" };
+                // Repos in the dashboard, branch prefixes otherwise
+                let slash = if app.dashboard { "" } else { "/" };
+                ListItem::new(Line::from(Span::styled(
+                    format!("{} {}{} ({})", arrow, name, slash, count),
+                    Style::default()
+                        .fg(Color::Blue)
+                        .add_modifier(Modifier::BOLD),
+                )))
+            }
+            ListRow::Worktree(i) => {
+                let wt = &app.worktrees[*i];
+                if !wt.is_bare {
+                    number += 1;
+                }
+                let item = worktree_item(
+                    wt,
+                    (!wt.is_bare && number <= 9).then_some(number),
+                    app.state.group_by_prefix && !app.dashboard,
+                    now,
+                    stale_after,
+                );
+                // Loaded from the cache and not yet re-checked against git
+                if app.revalidating.is_some() {
+                    item.style(Style::default().add_modifier(Modifier::DIM))
+                } else {
+                    item
+                }
+            }
         })
         .collect();
 
+    let mut title = if app.dashboard {
+        " All repositories".to_string()
+    } else {
+        " Worktrees".to_string()
+    };
+    if !app.filter.is_empty() {
+        title.push_str(&format!(" ({}/{})", app.visible.len(), app.worktrees.len()));
+    }
+    if app.state.sort != SortMode::Default {
+        title.push_str(&format!(" by {}", app.state.sort.label()));
+    }
+    if app.revalidating.is_some() {
+        title.push_str(" (refreshing…)");
+    }
+    title.push(' ');
+
     let list = List::new(items)
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .title(" Worktrees "),
-        )
+        .block(Block::default().borders(Borders::ALL).title(title))
         .highlight_style(
             Style::default()
                 .bg(Color::DarkGray)
@@ -96,3 +86,173 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
 
     frame.render_stateful_widget(list, area, &mut app.list_state.clone());
 }
+
+fn worktree_item(
+    wt: &Worktree,
+    number: Option<usize>,
+    grouped: bool,
+    now: i64,
+    stale_after: i64,
+) -> ListItem<'static> {
+    let number = number.map_or("  ".to_string(), |n| format!("{} ", n));
+
+    // The bare repository itself: no branch, checkout or notes to show
+    if wt.is_bare {
+        let name = wt.path.file_name().unwrap_or(wt.path.as_os_str()).to_string_lossy();
+        return ListItem::new(Line::from(vec![
+            Span::styled(number, Style::default().fg(Color::DarkGray)),
+            Span::styled(format!("      {} (bare)", name), Style::default().fg(Color::DarkGray)),
+        ]));
+    }
+
+    let (checked, total) = wt.status.progress;
+    let progress = if total > 0 {
+        format!("[{}/{}]", checked, total)
+    } else {
+        "[---]".to_string()
+    };
+
+    let branch_name = wt.branch.as_deref().unwrap_or("(detached)");
+    // A home worktree picked with set_home, or the only one in a bare layout
+    let main_marker = match (wt.is_main, wt.path == wt.repo) {
+        (true, true) => "(main)",
+        (true, false) => "(home)",
+        _ => "",
+    };
+
+    let is_merged = wt.is_merged();
+
+    // Indicator: ✓ for merged, space otherwise; dirty worktrees show counts instead
+    let indicator = if is_merged { "✓" } else { " " };
+
+    // Inside a group the prefix is already shown in the header
+    let display_name = if wt.is_main {
+        format!("{} {}", branch_name, main_marker)
+    } else if grouped && crate::sort::group_name(wt).is_some() {
+        format!("  {}", branch_name.split_once('/').map_or(branch_name, |(_, rest)| rest))
+    } else {
+        branch_name.to_string()
+    };
+
+    // Build ahead/behind indicator for non-main branches
+    let ahead_behind = if !wt.is_main && (wt.ahead > 0 || wt.behind > 0) {
+        format!(" ↑{}↓{}", wt.ahead, wt.behind)
+    } else {
+        String::new()
+    };
+
+    // Upstream indicator: ○ not pushed, ✗ upstream gone, ⇡N⇣M out of sync
+    let (upstream_marker, upstream_color) = match (&wt.upstream, &wt.branch) {
+        (None, Some(_)) => (" ○".to_string(), Color::DarkGray),
+        (Some(u), _) if u.gone => (" ✗".to_string(), Color::Red),
+        (Some(u), _) if u.ahead > 0 || u.behind > 0 => {
+            let mut marker = " ".to_string();
+            if u.ahead > 0 {
+                marker.push_str(&format!("⇡{}", u.ahead));
+            }
+            if u.behind > 0 {
+                marker.push_str(&format!("⇣{}", u.behind));
+            }
+            (marker, Color::Blue)
+        }
+        _ => (String::new(), Color::Reset),
+    };
+
+    // Stashes made on this branch, easy to forget since they're shared by all worktrees
+    let stash_marker = if wt.stashes > 0 {
+        format!(" ≡{}", wt.stashes)
+    } else {
+        String::new()
+    };
+
+    // Age of last activity; stale worktrees are highlighted as cleanup candidates
+    let last_activity = wt.last_activity();
+    let is_stale = !wt.is_main && last_activity.is_some_and(|t| now - t > stale_after);
+    let age = last_activity
+        .map(|t| crate::age::format_age(t, now))
+        .unwrap_or_else(|| "-".to_string());
+
+    // Color: green for main or merged branches, gray for stale, cyan for others
+    let branch_color = if wt.is_main || is_merged {
+        Color::Green
+    } else if is_stale {
+        Color::DarkGray
+    } else {
+        Color::Cyan
+    };
+
+    let mut spans = vec![
+        Span::styled(number, Style::default().fg(Color::DarkGray)),
+        Span::styled(
+            format!("{:>3} ", age),
+            if is_stale {
+                Style::default().fg(Color::Red)
+            } else {
+                Style::default().fg(Color::DarkGray)
+            },
+        ),
+        Span::styled(format!("{} ", indicator), Style::default().fg(Color::Green)),
+        Span::styled(
+            format!("{:<20}", display_name),
+            Style::default().fg(branch_color),
+        ),
+    ];
+    if let Some(op) = wt.operation {
+        spans.push(Span::styled(
+            format!(" {}", op.label()),
+            Style::default().fg(Color::Black).bg(Color::Red).add_modifier(Modifier::BOLD),
+        ));
+    }
+    if wt.has_changes {
+        spans.push(Span::raw(" "));
+        spans.extend(change_spans(&wt.changes, true));
+    }
+    spans.extend([
+        Span::styled(
+            ahead_behind,
+            Style::default().fg(Color::Magenta),
+        ),
+        Span::styled(upstream_marker, Style::default().fg(upstream_color)),
+        Span::styled(stash_marker, Style::default().fg(Color::LightCyan)),
+        Span::styled(format!(" {}", progress), Style::default().fg(Color::Yellow)),
+    ]);
+
+    ListItem::new(Line::from(spans))
+}
+
+/// Change counts: compact `+2 ~3 »1 ?1 !1` for the list, or spelled out
+pub fn change_spans(counts: &StatusCounts, compact: bool) -> Vec<Span<'static>> {
+    if counts.is_clean() {
+        return vec![Span::styled("Working tree clean", Style::default().fg(Color::Green))];
+    }
+
+    let kinds = [
+        (counts.staged, "+", "staged", Color::Green),
+        (counts.modified, "~", "modified", Color::Yellow),
+        (counts.renamed, "»", "renamed", Color::Cyan),
+        (counts.untracked, "?", "untracked", Color::DarkGray),
+        (counts.conflicted, "!", "conflicted", Color::Red),
+    ];
+    let parts: Vec<(String, Color)> = kinds
+        .into_iter()
+        .filter(|(n, ..)| *n > 0)
+        .map(|(n, symbol, word, color)| {
+            let text = if compact {
+                format!("{}{}", symbol, n)
+            } else {
+                format!("{} {}", n, word)
+            };
+            (text, color)
+        })
+        .collect();
+
+    let separator = if compact { " " } else { " · " };
+    let mut spans = Vec::new();
+    for (i, (text, color)) in parts.into_iter().enumerate() {
+        if i > 0 {
+            spans.push(Span::raw(separator));
+        }
+        spans.push(Span::styled(text, Style::default().fg(color)));
+    }
+    spans
+}
@@ -0,0 +1,508 @@
+use pulldown_cmark::{CodeBlockKind, Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+};
+
+/// Render markdown (as used in `.worktree-status.md`) into styled lines.
+/// Supports headings, emphasis, links, nested (task) lists, block quotes,
+/// fenced code with basic highlighting, tables and HTML comments.
+/// The list item starting on source line `cursor_line` (0-based) is
+/// highlighted and the index of its rendered line returned.
+pub fn render(
+    content: &str,
+    cursor_line: Option<usize>,
+) -> (Vec<Line<'static>>, Option<usize>) {
+    let options = Options::ENABLE_TABLES
+        | Options::ENABLE_TASKLISTS
+        | Options::ENABLE_STRIKETHROUGH
+        | Options::ENABLE_YAML_STYLE_METADATA_BLOCKS
+        | Options::ENABLE_PLUSES_DELIMITED_METADATA_BLOCKS;
+    let mut renderer = Renderer {
+        cursor_offset: cursor_line.and_then(|line| line_offset(content, line)),
+        ..Default::default()
+    };
+    for (event, range) in Parser::new_ext(content, options).into_offset_iter() {
+        renderer.event(event, range.start);
+    }
+    renderer.flush();
+
+    let mut lines = renderer.lines;
+    while lines.last().is_some_and(|l| l.width() == 0) {
+        lines.pop();
+    }
+    (lines, renderer.highlighted)
+}
+
+/// Byte offset of the first non-blank character on a 0-based line
+fn line_offset(content: &str, line: usize) -> Option<usize> {
+    let mut offset = 0;
+    for (i, raw) in content.split('\n').enumerate() {
+        if i == line {
+            return Some(offset + raw.len() - raw.trim_start().len());
+        }
+        offset += raw.len() + 1;
+    }
+    None
+}
+
+struct ListFrame {
+    next_number: Option<u64>, // None for bullet lists
+}
+
+#[derive(Default)]
+struct Table {
+    rows: Vec<Vec<Vec<Span<'static>>>>,
+    header_rows: usize,
+}
+
+#[derive(Default)]
+struct Renderer {
+    lines: Vec<Line<'static>>,
+    current: Vec<Span<'static>>,
+    styles: Vec<Style>,
+    lists: Vec<ListFrame>,
+    /// Bullet for the first line of the current list item, consumed on flush
+    item_marker: Option<Span<'static>>,
+    /// Items whose text is styled by a task marker, so End(Item) pops the style
+    task_items: Vec<bool>,
+    quote_depth: usize,
+    code_block: Option<(String, String)>, // (language, collected text)
+    table: Option<Table>,
+    link_urls: Vec<String>,
+    /// Inside a frontmatter block, which is shown separately from the notes
+    in_metadata: bool,
+    /// Source offset of the list item to highlight, and its rendered line once found
+    cursor_offset: Option<usize>,
+    highlight_pending: bool,
+    highlighted: Option<usize>,
+}
+
+impl Renderer {
+    fn style(&self) -> Style {
+        self.styles.last().copied().unwrap_or_default()
+    }
+
+    fn push_style(&mut self, patch: Style) {
+        let style = self.style().patch(patch);
+        self.styles.push(style);
+    }
+
+    fn push_text(&mut self, text: &str) {
+        let style = self.style();
+        if let Some(table) = &mut self.table {
+            if let Some(cell) = table.rows.last_mut().and_then(|row| row.last_mut()) {
+                cell.push(Span::styled(text.to_string(), style));
+            }
+            return;
+        }
+        self.current.push(Span::styled(text.to_string(), style));
+    }
+
+    /// Prefix for the next line: quote bars, then list indentation and bullet
+    fn prefix(&mut self) -> Vec<Span<'static>> {
+        let mut prefix = Vec::new();
+        for _ in 0..self.quote_depth {
+            prefix.push(Span::styled("│ ", Style::default().fg(Color::DarkGray)));
+        }
+        if !self.lists.is_empty() {
+            let depth = self.lists.len() - 1;
+            prefix.push(Span::raw("  ".repeat(depth)));
+            match self.item_marker.take() {
+                Some(marker) => prefix.push(marker),
+                None => prefix.push(Span::raw("  ")),
+            }
+        }
+        prefix
+    }
+
+    /// Finish the line being built, if any
+    fn flush(&mut self) {
+        if self.current.is_empty() && self.item_marker.is_none() {
+            return;
+        }
+        let mut spans = self.prefix();
+        spans.append(&mut self.current);
+        if self.highlight_pending {
+            self.highlight_pending = false;
+            self.highlighted = Some(self.lines.len());
+            let cursor = Style::default().bg(Color::DarkGray).add_modifier(Modifier::BOLD);
+            for span in &mut spans {
+                span.style = span.style.patch(cursor);
+            }
+        }
+        self.lines.push(Line::from(spans));
+    }
+
+    /// Separate blocks with one empty line
+    fn blank_line(&mut self) {
+        if self.lines.last().is_some_and(|l| l.width() > 0) {
+            self.lines.push(Line::default());
+        }
+    }
+
+    fn event(&mut self, event: Event, offset: usize) {
+        match event {
+            Event::Start(tag) => self.start(tag, offset),
+            Event::End(tag) => self.end(tag),
+            Event::Text(_) if self.in_metadata => {}
+            Event::Text(text) => {
+                if let Some((_, code)) = &mut self.code_block {
+                    code.push_str(&text);
+                } else {
+                    self.push_text(&text);
+                }
+            }
+            Event::Code(code) => {
+                self.push_style(Style::default().fg(Color::Magenta));
+                self.push_text(&code);
+                self.styles.pop();
+            }
+            Event::Html(html) => {
+                // Block HTML, most commonly multi-line <!-- comments -->
+                for line in html.lines() {
+                    self.flush();
+                    let mut spans = self.prefix();
+                    spans.push(Span::styled(line.to_string(), Style::default().fg(Color::DarkGray)));
+                    self.lines.push(Line::from(spans));
+                }
+            }
+            Event::InlineHtml(html) => {
+                self.push_style(Style::default().fg(Color::DarkGray));
+                self.push_text(&html);
+                self.styles.pop();
+            }
+            Event::SoftBreak | Event::HardBreak => self.flush(),
+            Event::Rule => {
+                self.flush();
+                self.blank_line();
+                self.lines.push(Line::from(Span::styled(
+                    "─".repeat(40),
+                    Style::default().fg(Color::DarkGray),
+                )));
+            }
+            Event::TaskListMarker(checked) => {
+                let (marker, style) = if checked {
+                    ("☑ ", Style::default().fg(Color::Green))
+                } else {
+                    ("☐ ", Style::default().fg(Color::Red))
+                };
+                self.item_marker = Some(Span::styled(marker, style));
+                if checked {
+                    self.push_style(Style::default().fg(Color::Green));
+                } else {
+                    self.push_style(Style::default());
+                }
+                if let Some(task) = self.task_items.last_mut() {
+                    *task = true;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn start(&mut self, tag: Tag, offset: usize) {
+        match tag {
+            Tag::Heading { level, .. } => {
+                self.flush();
+                self.blank_line();
+                let style = match level {
+                    HeadingLevel::H1 => Style::default()
+                        .fg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+                    HeadingLevel::H2 => Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+                    _ => Style::default().add_modifier(Modifier::BOLD),
+                };
+                self.push_style(style);
+            }
+            Tag::Paragraph => self.flush(),
+            Tag::MetadataBlock(_) => self.in_metadata = true,
+            Tag::BlockQuote(_) => {
+                self.flush();
+                self.quote_depth += 1;
+            }
+            Tag::CodeBlock(kind) => {
+                self.flush();
+                let lang = match kind {
+                    CodeBlockKind::Fenced(lang) => lang.to_string(),
+                    CodeBlockKind::Indented => String::new(),
+                };
+                self.code_block = Some((lang, String::new()));
+            }
+            Tag::List(start) => {
+                self.flush();
+                if self.lists.is_empty() {
+                    self.blank_line();
+                }
+                self.lists.push(ListFrame { next_number: start });
+            }
+            Tag::Item => {
+                self.flush();
+                let marker = match self.lists.last_mut().and_then(|l| l.next_number.as_mut()) {
+                    Some(n) => {
+                        let marker = format!("{}. ", n);
+                        *n += 1;
+                        marker
+                    }
+                    None => "• ".to_string(),
+                };
+                self.item_marker = Some(Span::styled(marker, Style::default().fg(Color::DarkGray)));
+                self.task_items.push(false);
+                self.highlight_pending = self.cursor_offset == Some(offset);
+            }
+            Tag::Emphasis => self.push_style(Style::default().add_modifier(Modifier::ITALIC)),
+            Tag::Strong => self.push_style(Style::default().add_modifier(Modifier::BOLD)),
+            Tag::Strikethrough => self.push_style(Style::default().add_modifier(Modifier::CROSSED_OUT)),
+            Tag::Link { dest_url, .. } => {
+                self.push_style(Style::default().fg(Color::Blue).add_modifier(Modifier::UNDERLINED));
+                self.link_urls.push(dest_url.to_string());
+            }
+            Tag::Table(_) => {
+                self.flush();
+                self.blank_line();
+                self.table = Some(Table::default());
+            }
+            Tag::TableHead => {
+                self.push_style(Style::default().add_modifier(Modifier::BOLD));
+                if let Some(table) = &mut self.table {
+                    table.rows.push(Vec::new());
+                }
+            }
+            Tag::TableRow => {
+                if let Some(table) = &mut self.table {
+                    table.rows.push(Vec::new());
+                }
+            }
+            Tag::TableCell => {
+                if let Some(row) = self.table.as_mut().and_then(|t| t.rows.last_mut()) {
+                    row.push(Vec::new());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn end(&mut self, tag: TagEnd) {
+        match tag {
+            TagEnd::Heading(_) => {
+                self.flush();
+                self.styles.pop();
+            }
+            TagEnd::MetadataBlock(_) => self.in_metadata = false,
+            TagEnd::Paragraph => {
+                self.flush();
+                if self.lists.is_empty() {
+                    self.blank_line();
+                }
+            }
+            TagEnd::BlockQuote(_) => {
+                self.flush();
+                self.quote_depth = self.quote_depth.saturating_sub(1);
+                self.blank_line();
+            }
+            TagEnd::CodeBlock => {
+                if let Some((lang, code)) = self.code_block.take() {
+                    for line in code.lines() {
+                        let mut spans = self.prefix();
+                        spans.push(Span::styled("▏ ", Style::default().fg(Color::DarkGray)));
+                        spans.extend(highlight_code(&lang, line));
+                        self.lines.push(Line::from(spans));
+                    }
+                }
+                if self.lists.is_empty() {
+                    self.blank_line();
+                }
+            }
+            TagEnd::HtmlBlock if self.lists.is_empty() => self.blank_line(),
+            TagEnd::List(_) => {
+                self.flush();
+                self.lists.pop();
+                if self.lists.is_empty() {
+                    self.blank_line();
+                }
+            }
+            TagEnd::Item => {
+                self.flush();
+                if self.task_items.pop() == Some(true) {
+                    self.styles.pop();
+                }
+            }
+            TagEnd::Emphasis | TagEnd::Strong | TagEnd::Strikethrough => {
+                self.styles.pop();
+            }
+            TagEnd::Link => {
+                self.styles.pop();
+                if let Some(url) = self.link_urls.pop() {
+                    if !url.is_empty() {
+                        self.push_style(Style::default().fg(Color::DarkGray));
+                        self.push_text(&format!(" ({})", url));
+                        self.styles.pop();
+                    }
+                }
+            }
+            TagEnd::TableHead => {
+                self.styles.pop();
+                if let Some(table) = &mut self.table {
+                    table.header_rows = table.rows.len();
+                }
+            }
+            TagEnd::Table => {
+                if let Some(table) = self.table.take() {
+                    self.render_table(table);
+                }
+                self.blank_line();
+            }
+            _ => {}
+        }
+    }
+
+    fn render_table(&mut self, table: Table) {
+        let cell_width = |cell: &Vec<Span>| cell.iter().map(|s| s.width()).sum::<usize>();
+        let columns = table.rows.iter().map(Vec::len).max().unwrap_or(0);
+        let widths: Vec<usize> = (0..columns)
+            .map(|c| {
+                table
+                    .rows
+                    .iter()
+                    .filter_map(|row| row.get(c))
+                    .map(cell_width)
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+        let border = Style::default().fg(Color::DarkGray);
+
+        for (i, row) in table.rows.into_iter().enumerate() {
+            let mut spans = self.prefix();
+            for (c, cell) in row.into_iter().enumerate() {
+                if c > 0 {
+                    spans.push(Span::styled(" │ ", border));
+                }
+                let padding = widths[c].saturating_sub(cell_width(&cell));
+                spans.extend(cell);
+                spans.push(Span::raw(" ".repeat(padding)));
+            }
+            self.lines.push(Line::from(spans));
+
+            if i + 1 == table.header_rows {
+                let separator: Vec<String> = widths.iter().map(|w| "─".repeat(*w)).collect();
+                let mut spans = self.prefix();
+                spans.push(Span::styled(separator.join("─┼─"), border));
+                self.lines.push(Line::from(spans));
+            }
+        }
+    }
+}
+
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "case", "class", "const", "continue", "def", "do", "done",
+    "elif", "else", "enum", "esac", "export", "false", "fi", "fn", "for", "from", "func",
+    "function", "if", "impl", "import", "in", "let", "local", "match", "mod", "mut", "nil",
+    "None", "null", "pub", "return", "self", "Self", "struct", "then", "trait", "true", "type",
+    "use", "var", "where", "while",
+];
+
+/// Lightweight, language-agnostic highlighting: comments, strings, numbers
+/// and common keywords. Good enough for snippets in notes.
+fn highlight_code(lang: &str, line: &str) -> Vec<Span<'static>> {
+    let comment_prefix = match lang {
+        "sh" | "bash" | "zsh" | "fish" | "shell" | "python" | "py" | "toml" | "yaml" | "yml"
+        | "ruby" | "rb" => "#",
+        "sql" | "lua" => "--",
+        _ => "//",
+    };
+    let code_style = Style::default().fg(Color::White);
+    let mut spans = Vec::new();
+    let mut word = String::new();
+    let mut chars = line.char_indices().peekable();
+
+    let flush_word = |word: &mut String, spans: &mut Vec<Span<'static>>| {
+        if word.is_empty() {
+            return;
+        }
+        let style = if KEYWORDS.contains(&word.as_str()) {
+            Style::default().fg(Color::Magenta)
+        } else if word.chars().next().is_some_and(|c| c.is_ascii_digit()) {
+            Style::default().fg(Color::Yellow)
+        } else {
+            code_style
+        };
+        spans.push(Span::styled(std::mem::take(word), style));
+    };
+
+    while let Some((i, c)) = chars.next() {
+        if line[i..].starts_with(comment_prefix) {
+            flush_word(&mut word, &mut spans);
+            spans.push(Span::styled(line[i..].to_string(), Style::default().fg(Color::DarkGray)));
+            return spans;
+        }
+        if c == '"' || c == '\'' || c == '`' {
+            flush_word(&mut word, &mut spans);
+            let mut end = line.len();
+            while let Some((j, d)) = chars.next() {
+                if d == '\\' {
+                    chars.next();
+                } else if d == c {
+                    end = j + d.len_utf8();
+                    break;
+                }
+            }
+            spans.push(Span::styled(line[i..end].to_string(), Style::default().fg(Color::Green)));
+        } else if c.is_alphanumeric() || c == '_' {
+            word.push(c);
+        } else {
+            flush_word(&mut word, &mut spans);
+            spans.push(Span::styled(c.to_string(), code_style));
+        }
+    }
+    flush_word(&mut word, &mut spans);
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(lines: &[Line]) -> Vec<String> {
+        lines
+            .iter()
+            .map(|l| l.spans.iter().map(|s| s.content.as_ref()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_nested_task_list() {
+        let lines = render("## Status\n- [x] Done\n- [ ] Parent\n  - [ ] Child\n", None).0;
+        assert_eq!(text(&lines), vec!["Status", "", "☑ Done", "☐ Parent", "  ☐ Child"]);
+    }
+
+    #[test]
+    fn test_cursor_highlights_item() {
+        let (lines, highlighted) = render("## Status\n- [x] Done\n- [ ] Todo\n", Some(2));
+        assert_eq!(highlighted, Some(3));
+        assert_eq!(text(&lines)[3], "☐ Todo");
+    }
+
+    #[test]
+    fn test_multiline_comment_and_code() {
+        let lines = render("<!--\nhidden\n-->\n\n```rust\nlet x = 1;\n```\n", None).0;
+        let text = text(&lines);
+        assert_eq!(&text[..3], &["<!--", "hidden", "-->"]);
+        assert!(text.contains(&"▏ let x = 1;".to_string()));
+    }
+
+    #[test]
+    fn test_table_columns_are_aligned() {
+        let lines = render("| a | long |\n|---|---|\n| ccc | d |\n", None).0;
+        let text = text(&lines);
+        assert_eq!(text[0].trim_end(), "a   │ long");
+        assert_eq!(text[2].trim_end(), "ccc │ d");
+    }
+
+    #[test]
+    fn test_frontmatter_is_hidden() {
+        let lines = render("---\nowner: alice\n---\n# Title\n", None).0;
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0].to_string(), "Title");
+    }
+}
@@ -1,9 +1,14 @@
 mod detail;
+mod diff;
 mod dialogs;
 mod layout;
 mod list;
+mod markdown;
 
-use ratatui::Frame;
+use std::io;
+
+use crossterm::event::{DisableMouseCapture, EnableMouseCapture};
+use ratatui::{text::Span, DefaultTerminal, Frame};
 
 use crate::app::{App, AppMode};
 
@@ -36,6 +41,45 @@ pub fn render(frame: &mut Frame, app: &App) {
         AppMode::Help => {
             dialogs::render_help(frame, app);
         }
-        AppMode::Normal => {}
+        AppMode::History => {
+            dialogs::render_history(frame, app);
+        }
+        AppMode::AddingTask => {
+            dialogs::render_add_task_dialog(frame, app);
+        }
+        AppMode::Normal | AppMode::Filtering | AppMode::Tasks | AppMode::EditingSection
+        | AppMode::Log | AppMode::Changes => {}
+        AppMode::ConfirmDiscard => {
+            dialogs::render_discard_dialog(frame, app);
+        }
+        AppMode::Committing => {
+            dialogs::render_commit_dialog(frame, app);
+        }
+        AppMode::Stashes => {
+            dialogs::render_stashes(frame, app);
+        }
+        AppMode::ConfirmAbort => {
+            dialogs::render_abort_dialog(frame, app);
+        }
     }
 }
+
+/// Enter the TUI, with mouse capture for wheel scrolling
+pub fn init_terminal() -> DefaultTerminal {
+    let terminal = ratatui::init();
+    let _ = crossterm::execute!(io::stdout(), EnableMouseCapture);
+    terminal
+}
+
+/// Leave the TUI (before exiting or handing the terminal to another program)
+pub fn restore_terminal() {
+    let _ = crossterm::execute!(io::stdout(), DisableMouseCapture);
+    ratatui::restore();
+}
+
+/// Terminal columns taken by the first `chars` chars of `text`, i.e. where
+/// the cursor goes; wide chars (CJK, most emoji) take two
+fn cursor_column(text: &str, chars: usize) -> u16 {
+    let end = text.char_indices().nth(chars).map_or(text.len(), |(i, _)| i);
+    Span::raw(&text[..end]).width() as u16
+}
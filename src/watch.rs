@@ -0,0 +1,144 @@
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Receiver};
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use notify::{Event, RecommendedWatcher, RecursiveMode, Watcher as _};
+
+use crate::config::NotesStorage;
+use crate::git::Worktree;
+
+/// Wait this long after the last event before refreshing, so a commit (which
+/// touches the index, HEAD and logs in quick succession) refreshes once
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Watches each worktree's status file and git dir (HEAD, index, in-progress
+/// operation markers) and its `logs` dir (new commits), reporting which
+/// worktrees changed. Directories are watched rather than files because
+/// editors and git replace files by renaming over them.
+pub struct Watcher {
+    watcher: RecommendedWatcher,
+    events: Receiver<notify::Result<Event>>,
+    watched: HashSet<PathBuf>,
+    targets: Vec<Target>,
+    pending: HashMap<PathBuf, Instant>, // worktree path -> last event
+}
+
+struct Target {
+    worktree: PathBuf,
+    git_dir: PathBuf,
+    status_file: PathBuf,
+}
+
+impl Target {
+    fn matches(&self, path: &Path) -> bool {
+        if path == self.status_file {
+            return true;
+        }
+        let Some(parent) = path.parent() else {
+            return false;
+        };
+        let is_lock = path.extension().is_some_and(|e| e == "lock");
+        // Top-level git dir entries (HEAD, index, MERGE_HEAD, rebase-merge, ...)
+        // and logs/HEAD, which is appended to on every commit
+        !is_lock && (parent == self.git_dir || path == self.git_dir.join("logs").join("HEAD"))
+    }
+}
+
+impl Watcher {
+    pub fn new() -> Result<Self> {
+        let (tx, events) = channel();
+        let watcher = notify::recommended_watcher(move |event| {
+            let _ = tx.send(event);
+        })?;
+        Ok(Self {
+            watcher,
+            events,
+            watched: HashSet::new(),
+            targets: Vec::new(),
+            pending: HashMap::new(),
+        })
+    }
+
+    /// Watch the given worktrees, dropping watches of ones that are gone
+    pub fn watch(&mut self, worktrees: &[Worktree], notes: NotesStorage) {
+        self.targets = worktrees
+            .iter()
+            .filter_map(|wt| {
+                Some(Target {
+                    worktree: wt.path.clone(),
+                    git_dir: crate::git::git_dir(&wt.path)?,
+                    status_file: crate::status::status_path(notes, &wt.path, wt.branch.as_deref()),
+                })
+            })
+            .collect();
+
+        let dirs: HashSet<PathBuf> = self
+            .targets
+            .iter()
+            .flat_map(|t| {
+                let status_dir = t.status_file.parent().map(Path::to_path_buf);
+                [Some(t.git_dir.clone()), Some(t.git_dir.join("logs")), status_dir]
+            })
+            .flatten()
+            .filter(|dir| dir.is_dir())
+            .collect();
+
+        for dir in self.watched.difference(&dirs) {
+            let _ = self.watcher.unwatch(dir);
+        }
+        for dir in dirs.difference(&self.watched) {
+            let _ = self.watcher.watch(dir, RecursiveMode::NonRecursive);
+        }
+        self.watched = dirs;
+    }
+
+    /// Worktrees that changed and have been quiet for `DEBOUNCE`
+    pub fn changed(&mut self) -> Vec<PathBuf> {
+        let now = Instant::now();
+        while let Ok(event) = self.events.try_recv() {
+            let Ok(event) = event else {
+                continue;
+            };
+            if event.kind.is_access() {
+                continue;
+            }
+            for path in &event.paths {
+                for target in self.targets.iter().filter(|t| t.matches(path)) {
+                    self.pending.insert(target.worktree.clone(), now);
+                }
+            }
+        }
+
+        let ready: Vec<PathBuf> = self
+            .pending
+            .iter()
+            .filter(|(_, last)| now.duration_since(**last) >= DEBOUNCE)
+            .map(|(path, _)| path.clone())
+            .collect();
+        for path in &ready {
+            self.pending.remove(path);
+        }
+        ready
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_target_matches() {
+        let target = Target {
+            worktree: PathBuf::from("/src/app-feature"),
+            git_dir: PathBuf::from("/src/app/.git/worktrees/app-feature"),
+            status_file: PathBuf::from("/src/app-feature/.worktree-status.md"),
+        };
+        assert!(target.matches(Path::new("/src/app-feature/.worktree-status.md")));
+        assert!(target.matches(Path::new("/src/app/.git/worktrees/app-feature/index")));
+        assert!(target.matches(Path::new("/src/app/.git/worktrees/app-feature/logs/HEAD")));
+        assert!(!target.matches(Path::new("/src/app/.git/worktrees/app-feature/index.lock")));
+        assert!(!target.matches(Path::new("/src/app-feature/README.md")));
+    }
+}